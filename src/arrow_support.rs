@@ -0,0 +1,248 @@
+//! Conversion of data sets and predictions to Arrow record batches
+
+use std::sync::Arc;
+
+use arff::dynamic::{Column, Value};
+use arrow::array::{
+    ArrayRef, DictionaryArray, Float64Array, Int32Array, Int64Array, StringArray, UInt64Array,
+};
+use arrow::datatypes::{Field, Int32Type, Schema};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+
+use dataset::{DataSet, FeatureType};
+use error::{Error, Result};
+use predictions::Predictions;
+
+impl DataSet {
+    /// convert the data set to an Arrow record batch
+    ///
+    /// All columns in `data()` are converted, including the target and excluded columns. Numeric
+    /// columns become `Float64` arrays, nominal columns dictionary arrays of their levels, and
+    /// string columns `Utf8` arrays. Missing values are null. The feature columns of sparse data
+    /// sets can not be converted.
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        if self.is_sparse() {
+            return Err(Error::ArrowError(ArrowError::InvalidArgumentError(
+                "sparse data sets can not be converted to record batches".to_owned(),
+            )));
+        }
+
+        let arff = self.data();
+        let columns: Vec<_> = (0..arff.n_cols()).map(|c| arff.col(c)).collect();
+
+        let arrays: Vec<ArrayRef> = columns
+            .iter()
+            .map(|col| self.column_array(col))
+            .collect::<Result<_>>()?;
+
+        let fields: Vec<_> = columns
+            .iter()
+            .zip(&arrays)
+            .map(|(col, array)| Field::new(col.name(), array.data_type().clone(), true))
+            .collect();
+
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
+
+    fn column_array(&self, col: &Column) -> Result<ArrayRef> {
+        let values = (0..col.len()).map(|i| col.item(i));
+
+        Ok(match self.column_type(col) {
+            FeatureType::Nominal => {
+                let keys: Int32Array = values
+                    .map(|v| match v {
+                        Value::Nominal(c, _) => Some(c as i32),
+                        _ => None,
+                    })
+                    .collect();
+                let levels = self.nominal_levels(col).unwrap_or_default();
+                let levels: ArrayRef = Arc::new(StringArray::from_iter_values(&levels));
+                Arc::new(DictionaryArray::<Int32Type>::try_new(keys, levels)?)
+            }
+            FeatureType::String | FeatureType::Date => {
+                let strings: StringArray = values
+                    .map(|v| match v {
+                        Value::String(s) => Some(s),
+                        _ => None,
+                    })
+                    .collect();
+                Arc::new(strings)
+            }
+            FeatureType::Numeric => {
+                let numbers: Float64Array = values.map(|v| v.as_f64().ok()).collect();
+                Arc::new(numbers)
+            }
+        })
+    }
+}
+
+impl<Y: ArrowColumn> Predictions<Y> {
+    /// convert recorded predictions to an Arrow record batch with the columns `repeat`, `fold`,
+    /// `row_id`, `prediction` and `truth`. For classification tasks, predictions given as class
+    /// indices become dictionary arrays of the class labels.
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        let repeat = UInt64Array::from_iter_values(self.iter().map(|p| p.repeat as u64));
+        let fold = UInt64Array::from_iter_values(self.iter().map(|p| p.fold as u64));
+        let row_id = UInt64Array::from_iter_values(self.iter().map(|p| p.row_id as u64));
+
+        let labels = self.class_labels();
+        let predicted: Vec<_> = self.iter().map(|p| &p.predicted).collect();
+        let truth: Vec<_> = self.iter().map(|p| &p.truth).collect();
+
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(repeat),
+            Arc::new(fold),
+            Arc::new(row_id),
+            Y::to_array(&predicted, labels)?,
+            Y::to_array(&truth, labels)?,
+        ];
+
+        let names = ["repeat", "fold", "row_id", "prediction", "truth"];
+        let fields: Vec<_> = names
+            .iter()
+            .zip(&arrays)
+            .map(|(name, array)| Field::new(*name, array.data_type().clone(), false))
+            .collect();
+
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
+}
+
+/// Types of predictions that can be converted to Arrow arrays
+pub trait ArrowColumn: Sized {
+    /// convert values to an array; integer values are mapped to `labels` if given
+    fn to_array(values: &[&Self], labels: Option<&[String]>) -> Result<ArrayRef>;
+}
+
+macro_rules! impl_index_arrow_column {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl ArrowColumn for $t {
+                fn to_array(values: &[&Self], labels: Option<&[String]>) -> Result<ArrayRef> {
+                    match labels {
+                        Some(labels) => {
+                            let keys = values.iter().map(|&&v| v as i32);
+                            let keys = Int32Array::from_iter_values(keys);
+                            let labels: ArrayRef =
+                                Arc::new(StringArray::from_iter_values(labels));
+                            let dict = DictionaryArray::<Int32Type>::try_new(keys, labels)?;
+                            Ok(Arc::new(dict))
+                        }
+                        None => Ok(Arc::new(Int64Array::from_iter_values(
+                            values.iter().map(|&&v| v as i64),
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_index_arrow_column!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl ArrowColumn for f32 {
+    fn to_array(values: &[&Self], _: Option<&[String]>) -> Result<ArrayRef> {
+        Ok(Arc::new(Float64Array::from_iter_values(values.iter().map(|&&v| v as f64))))
+    }
+}
+
+impl ArrowColumn for f64 {
+    fn to_array(values: &[&Self], _: Option<&[String]>) -> Result<ArrayRef> {
+        Ok(Arc::new(Float64Array::from_iter_values(values.iter().cloned().cloned())))
+    }
+}
+
+impl ArrowColumn for String {
+    fn to_array(values: &[&Self], _: Option<&[String]>) -> Result<ArrayRef> {
+        Ok(Arc::new(StringArray::from_iter_values(values)))
+    }
+}
+
+#[test]
+fn data_set_record_batch() {
+    use arff::dynamic::DataSet as ArffDataSet;
+    use arrow::array::Array;
+    use arrow::datatypes::DataType;
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE note STRING\n\
+         @ATTRIBUTE class {a, b}\n\
+         @DATA\n\
+         1.5, 'one', b\n\
+         ?, ?, a\n",
+    ).unwrap();
+    let batch = DataSet::from_arff(arff, Some("class")).to_record_batch().unwrap();
+
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.schema().field(0).data_type(), &DataType::Float64);
+    assert_eq!(batch.schema().field(1).data_type(), &DataType::Utf8);
+    assert_eq!(
+        batch.schema().field(2).data_type(),
+        &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+    );
+    assert_eq!(batch.column(0).null_count(), 1);
+    assert_eq!(batch.column(1).null_count(), 1);
+}
+
+#[test]
+fn nominal_record_batch_columns() {
+    use arff::dynamic::DataSet as ArffDataSet;
+    use dataset::{test_feature, FeatureInfo};
+    use arrow::array::{Array, AsArray};
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE kind {x, 'y'}\n\
+         @ATTRIBUTE class {'neg', 'pos'}\n\
+         @DATA\n\
+         ?, 'pos'\n\
+         ?, 'neg'\n",
+    ).unwrap();
+    let mut data = DataSet::from_arff(arff, Some("class"));
+    data.features = vec![
+        test_feature("kind", FeatureType::Nominal, &["x", "y"]),
+        FeatureInfo { index: 1, ..test_feature("class", FeatureType::Nominal, &["neg", "pos"]) },
+    ];
+    let batch = data.to_record_batch().unwrap();
+
+    let kind = batch.column(0).as_dictionary::<Int32Type>();
+    assert_eq!(kind.null_count(), 2);
+    assert_eq!(
+        kind.values().as_string::<i32>().iter().collect::<Vec<_>>(),
+        [Some("x"), Some("y")]
+    );
+
+    let class = batch.column(1).as_dictionary::<Int32Type>();
+    assert_eq!(class.keys().values().to_vec(), vec![1, 0]);
+    assert_eq!(
+        class.values().as_string::<i32>().iter().collect::<Vec<_>>(),
+        [Some("neg"), Some("pos")]
+    );
+}
+
+#[test]
+fn predictions_record_batch() {
+    use arrow::datatypes::DataType;
+    use procedures::Fold;
+
+    let labels = vec!["neg".to_owned(), "pos".to_owned()];
+    let mut predictions = Predictions::new("1", Some(labels));
+    predictions.record(&Fold::new(0, 1), 5, &1usize, 0);
+
+    let batch = predictions.to_record_batch().unwrap();
+
+    assert_eq!(batch.num_rows(), 1);
+    assert_eq!(batch.num_columns(), 5);
+    assert_eq!(
+        batch.schema().field(3).data_type(),
+        &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+    );
+
+    let mut regression = Predictions::new("2", None);
+    regression.record(&Fold::new(0, 0), 0, &2.5, 3.0);
+    let batch = regression.to_record_batch().unwrap();
+    assert_eq!(batch.schema().field(4).data_type(), &DataType::Float64);
+}
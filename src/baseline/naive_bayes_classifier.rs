@@ -7,6 +7,9 @@ use std::fmt;
 use std::hash::Hash;
 use std::iter::FromIterator;
 
+use flow::{DescribeFlow, Flow};
+use learner::Classifier;
+
 /// A Gaussian Naive Bayes Classifier
 ///
 /// The classifier is trained by consuming an iterator over the training data:
@@ -70,6 +73,13 @@ where
 impl<C> NaiveBayesClassifier<C>
 where  C: Eq + Hash + Copy,
 {
+    /// create an untrained classifier, to be trained with `Classifier::fit`
+    pub fn new() -> Self {
+        NaiveBayesClassifier {
+            class_distributions: HashMap::new()
+        }
+    }
+
     /// predict target class for a single feature vector
     pub fn predict(&self, x: &[f64]) -> C {
         self.class_distributions
@@ -95,6 +105,38 @@ where  C: Eq + Hash + Copy,
     }
 }
 
+impl<C> Default for NaiveBayesClassifier<C>
+where  C: Eq + Hash + Copy,
+{
+    fn default() -> Self {
+        NaiveBayesClassifier::new()
+    }
+}
+
+impl<C> Classifier<f64, C> for NaiveBayesClassifier<C>
+where  C: Eq + Hash + Copy,
+{
+    fn fit(&mut self, x: &[&[f64]], y: &[&C]) {
+        *self = x.iter().zip(y).map(|(&x, &y)| (x, y)).collect();
+    }
+
+    fn predict(&self, x: &[f64]) -> C {
+        NaiveBayesClassifier::predict(self, x)
+    }
+}
+
+impl<C> DescribeFlow for NaiveBayesClassifier<C>
+where C: Eq + Hash,
+{
+    fn describe_flow(&self) -> Flow {
+        Flow::new(
+            "openml-rust.baseline.NaiveBayesClassifier",
+            concat!("openml==", env!("CARGO_PKG_VERSION")),
+        ).with_description("Gaussian Naive Bayes Classifier")
+            .with_dependency(concat!("openml==", env!("CARGO_PKG_VERSION")))
+    }
+}
+
 impl FeatureDistribution {
     fn new() -> Self {
         FeatureDistribution {
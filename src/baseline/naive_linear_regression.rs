@@ -3,6 +3,9 @@
 use std::f64;
 use std::iter::FromIterator;
 
+use flow::{DescribeFlow, Flow};
+use learner::Regressor;
+
 /// A Naive Linear Regression model
 ///
 /// This is univariate regression on a single feature. During training the best feature is selected.
@@ -96,12 +99,51 @@ impl<'a, J> FromIterator<(J, &'a f64)> for NaiveLinearRegression
 
 impl NaiveLinearRegression
 {
+    /// create an untrained model, to be trained with `Regressor::fit`. Untrained models predict
+    /// `NaN`.
+    pub fn new() -> Self {
+        NaiveLinearRegression {
+            slope: f64::NAN,
+            intercept: f64::NAN,
+            feature: 0,
+        }
+    }
+
     /// predict target value for a single feature vector
     pub fn predict(&self, x: &[f64]) -> f64 {
         self.intercept + x[self.feature] * self.slope
     }
 }
 
+impl Default for NaiveLinearRegression
+{
+    fn default() -> Self {
+        NaiveLinearRegression::new()
+    }
+}
+
+impl Regressor<f64> for NaiveLinearRegression
+{
+    fn fit(&mut self, x: &[&[f64]], y: &[&f64]) {
+        *self = x.iter().zip(y).map(|(&x, &y)| (x, y)).collect();
+    }
+
+    fn predict(&self, x: &[f64]) -> f64 {
+        NaiveLinearRegression::predict(self, x)
+    }
+}
+
+impl DescribeFlow for NaiveLinearRegression
+{
+    fn describe_flow(&self) -> Flow {
+        Flow::new(
+            "openml-rust.baseline.NaiveLinearRegression",
+            concat!("openml==", env!("CARGO_PKG_VERSION")),
+        ).with_description("Univariate linear regression on the best single feature")
+            .with_dependency(concat!("openml==", env!("CARGO_PKG_VERSION")))
+    }
+}
+
 #[test]
 fn nbc_flat() {
     let data = vec![(vec![1.0, 2.0], 3.0),
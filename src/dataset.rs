@@ -1,22 +1,496 @@
-use arff::dynamic::DataSet as ArffDataSet;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use arff::dynamic::{Column, DataSet as ArffDataSet, Value};
+
+use error::{Error, Result};
+use sparse::SparseFeatures;
+
+/// Status of a data set on OpenML
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DatasetStatus {
+    #[default]
+    Active,
+    Deprecated,
+    InPreparation,
+}
+
+impl FromStr for DatasetStatus {
+    type Err = Error;
+
+    /// parse status as used by the OpenML API
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "active" => Ok(DatasetStatus::Active),
+            "deactivated" | "deprecated" => Ok(DatasetStatus::Deprecated),
+            "in_preparation" | "in preparation" => Ok(DatasetStatus::InPreparation),
+            _ => Err(Error::ParseError(format!("unknown data set status '{}'", s))),
+        }
+    }
+}
+
+impl DatasetStatus {
+    /// status as used by the OpenML API
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            DatasetStatus::Active => "active",
+            DatasetStatus::Deprecated => "deactivated",
+            DatasetStatus::InPreparation => "in_preparation",
+        }
+    }
+}
+
+/// Meta-data of a data set, as provided by OpenML
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DatasetDescription {
+    pub id: u32,
+    pub name: String,
+    pub version: u32,
+    pub description: String,
+    pub format: String,
+    pub creators: Vec<String>,
+    pub contributors: Vec<String>,
+    pub collection_date: Option<String>,
+    pub upload_date: Option<String>,
+    pub licence: Option<String>,
+    pub citation: Option<String>,
+    pub original_data_url: Option<String>,
+    pub status: DatasetStatus,
+    pub default_target_attribute: Option<String>,
+    pub row_id_attribute: Option<String>,
+    pub ignore_attributes: Vec<String>,
+    pub tags: Vec<String>,
+
+    /// URL of the ARFF file
+    pub url: String,
+
+    /// URL of the parquet file, if available
+    pub parquet_url: Option<String>,
+}
+
+/// Data type of a feature
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FeatureType {
+    Numeric,
+    Nominal,
+    String,
+    Date,
+}
+
+impl FromStr for FeatureType {
+    type Err = Error;
+
+    /// parse data type as used by the OpenML API
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "numeric" => Ok(FeatureType::Numeric),
+            "nominal" => Ok(FeatureType::Nominal),
+            "string" => Ok(FeatureType::String),
+            "date" => Ok(FeatureType::Date),
+            _ => Err(Error::ParseError(format!("unknown feature type '{}'", s))),
+        }
+    }
+}
+
+/// Information about a single column of a data set
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureInfo {
+    pub index: usize,
+    pub name: String,
+    pub data_type: FeatureType,
+
+    /// levels of a nominal feature, in the order of the ARFF declaration
+    pub nominal_values: Vec<String>,
+
+    pub is_target: bool,
+    pub is_ignore: bool,
+    pub is_row_identifier: bool,
+    pub number_of_missing_values: u64,
+}
 
 /// An arbitrary data set
+///
+/// Data sets are usually loaded as part of a task, but can also be loaded directly:
+/// ```no_run
+/// # use openml::DataSet;
+/// let iris = DataSet::by_name("iris", None).unwrap();
+/// println!("{} rows", iris.n_rows());
+/// ```
 #[derive(Debug)]
-pub(crate) struct DataSet {
+pub struct DataSet {
+    pub(crate) description: Option<DatasetDescription>,
+    pub(crate) features: Vec<FeatureInfo>,
     pub(crate) arff: ArffDataSet,
     pub(crate) target: Option<String>,
+
+    /// feature columns of a sparse data set; `arff` then only holds the remaining columns
+    pub(crate) sparse: Option<SparseFeatures>,
 }
 
 impl DataSet {
+    /// create a data set from ARFF data, e.g. to define tasks on data that is not on OpenML
+    pub fn from_arff(arff: ArffDataSet, target: Option<&str>) -> Self {
+        DataSet {
+            description: None,
+            features: vec![],
+            arff,
+            target: target.map(str::to_owned),
+            sparse: None,
+        }
+    }
+
+    /// get data set ID, if the data set was loaded from OpenML
+    pub fn id(&self) -> Option<u32> {
+        self.description.as_ref().map(|d| d.id)
+    }
+
+    /// get OpenML meta-data, if the data set was loaded from OpenML
+    pub fn description(&self) -> Option<&DatasetDescription> {
+        self.description.as_ref()
+    }
+
+    /// get per-column information, if the data set was loaded from OpenML
+    pub fn features(&self) -> &[FeatureInfo] {
+        &self.features
+    }
+
+    /// get the name of the target column
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// use a different column as target. For sparse data sets, only columns in `data()` can be
+    /// used as target.
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_owned());
+        self
+    }
+
+    /// get the raw data. For sparse data sets, this only contains the columns that are not
+    /// features, such as the target.
+    pub fn data(&self) -> &ArffDataSet {
+        &self.arff
+    }
+
+    /// get the feature rows of a sparse data set, or `None` if the data set is dense
+    pub fn sparse_features(&self) -> Option<&SparseFeatures> {
+        self.sparse.as_ref()
+    }
+
+    /// `true` if the data set was stored in sparse format
+    pub fn is_sparse(&self) -> bool {
+        self.sparse.is_some()
+    }
+
+    /// get number of rows
+    pub fn n_rows(&self) -> usize {
+        self.arff.n_rows()
+    }
+
+    /// get number of columns, including the target
+    pub fn n_cols(&self) -> usize {
+        self.arff.n_cols() + self.sparse.as_ref().map_or(0, SparseFeatures::n_cols)
+    }
+
+    /// get the names of columns that are neither features nor target: the row ID attribute and
+    /// ignored attributes.
+    pub fn excluded_columns(&self) -> Vec<&str> {
+        let description = match self.description {
+            Some(ref d) => d,
+            None => return vec![],
+        };
+
+        description
+            .row_id_attribute
+            .iter()
+            .chain(description.ignore_attributes.iter())
+            .map(String::as_str)
+            .filter(|&col| Some(col) != self.target())
+            .collect()
+    }
+
     /// return two `ArffDataSet`s; one containing the features and the other containing the target
-    /// variable.
-    pub(crate) fn clone_split(&self) -> Option<(ArffDataSet, ArffDataSet)> {
+    /// variable. Columns listed by `excluded_columns` are not part of the features.
+    pub fn clone_split(&self) -> Option<(ArffDataSet, ArffDataSet)> {
         match self.target {
             None => None,
             Some(ref col) => {
                 let data = self.arff.clone();
-                Some(data.split_one(col))
+                let (data, target) = data.split_one(col);
+                let excluded: HashSet<_> = self.excluded_columns().into_iter().collect();
+                let (features, _) = data.split(excluded);
+                Some((features, target))
             }
         }
     }
+
+    /// return an `ArffDataSet` with the columns listed by `excluded_columns`, such as row IDs.
+    pub fn clone_excluded(&self) -> ArffDataSet {
+        let excluded: HashSet<_> = self.excluded_columns().into_iter().collect();
+        let (_, excluded) = self.arff.clone().split(excluded);
+        excluded
+    }
+
+    /// get a mask that is `true` for every row with at least one missing feature value. The target
+    /// and columns listed by `excluded_columns` are not considered.
+    pub fn missing_mask(&self) -> Vec<bool> {
+        let excluded = self.excluded_columns();
+        let mut mask = vec![false; self.n_rows()];
+        for c in 0..self.arff.n_cols() {
+            let col = self.arff.col(c);
+            if Some(col.name()) == self.target() || excluded.contains(&col.name()) {
+                continue;
+            }
+            for (i, m) in mask.iter_mut().enumerate() {
+                *m = *m || is_missing(&col.item(i));
+            }
+        }
+        if let Some(ref sparse) = self.sparse {
+            for (m, row) in mask.iter_mut().zip(sparse.rows()) {
+                *m = *m || row.iter().any(|&(_, v)| v.is_nan());
+            }
+        }
+        mask
+    }
+
+    /// return the categories of a nominal target column, or `None` if the target column is not
+    /// nominal.
+    pub(crate) fn target_categories(&self) -> Option<Vec<String>> {
+        self.nominal_levels(self.arff.col_by_name(self.target.as_ref()?))
+    }
+
+    /// get the type of a column in `data()`. The type is taken from the feature information if
+    /// available, and otherwise determined from the column's values; columns without any values
+    /// are then treated as numeric.
+    pub(crate) fn column_type(&self, col: &Column) -> FeatureType {
+        match self.feature(col.name()) {
+            Some(f) => f.data_type,
+            None => value_type(col).unwrap_or(FeatureType::Numeric),
+        }
+    }
+
+    /// get the levels of a nominal column in `data()`, in declaration order and without quotes.
+    /// The levels are taken from the feature information if available, and otherwise from the
+    /// column's values. Returns `None` if the column is not nominal.
+    pub(crate) fn nominal_levels(&self, col: &Column) -> Option<Vec<String>> {
+        match self.feature(col.name()) {
+            Some(f) if f.data_type != FeatureType::Nominal => return None,
+            Some(f) if !f.nominal_values.is_empty() => return Some(f.nominal_values.clone()),
+            _ => {}
+        }
+
+        (0..col.len())
+            .filter_map(|i| match col.item(i) {
+                Value::Nominal(_, levels) => Some(levels.iter().map(|l| unquote(l)).collect()),
+                _ => None,
+            })
+            .next()
+    }
+
+    fn feature(&self, name: &str) -> Option<&FeatureInfo> {
+        self.features.iter().find(|f| f.name == name)
+    }
+}
+
+/// quote a name or value for an ARFF file if necessary, so that the `arff` crate reads it back
+/// unchanged. Its parser does not support escape sequences, so values that contain both `'` and
+/// `"` can not be represented; they are written in double quotes and will not be read back.
+///
+/// Nominal levels are quoted the same way in the declaration and in the data. The `arff` crate
+/// matches values to the declared levels verbatim, without removing quotes, so it reads quoted
+/// levels back only if they contain neither whitespace nor commas.
+pub(crate) fn quote(s: &str) -> String {
+    if !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || ",{}%'\"".contains(c)) {
+        s.to_owned()
+    } else if s.contains('\'') {
+        format!("\"{}\"", s)
+    } else {
+        format!("'{}'", s)
+    }
+}
+
+/// remove the quotes around an ARFF name or value written by `quote`
+pub(crate) fn unquote(s: &str) -> String {
+    match s.chars().next() {
+        Some(q) if (q == '\'' || q == '"') && s.len() > 1 && s.ends_with(q) => {
+            s[1..s.len() - 1].to_owned()
+        }
+        _ => s.to_owned(),
+    }
+}
+
+pub(crate) fn is_missing(v: &Value) -> bool {
+    matches!(*v, Value::Missing)
+}
+
+/// determine the type of a column from its values. Returns `None` if all values are missing.
+fn value_type(col: &Column) -> Option<FeatureType> {
+    for i in 0..col.len() {
+        match col.item(i) {
+            Value::Missing => continue,
+            Value::Nominal(..) => return Some(FeatureType::Nominal),
+            Value::String(_) => return Some(FeatureType::String),
+            _ => return Some(FeatureType::Numeric),
+        }
+    }
+    None
+}
+
+/// feature information for tests
+#[cfg(test)]
+pub(crate) fn test_feature(name: &str, data_type: FeatureType, levels: &[&str]) -> FeatureInfo {
+    FeatureInfo {
+        index: 0,
+        name: name.to_owned(),
+        data_type,
+        nominal_values: levels.iter().map(|&l| l.to_owned()).collect(),
+        is_target: false,
+        is_ignore: false,
+        is_row_identifier: false,
+        number_of_missing_values: 0,
+    }
+}
+
+#[test]
+fn quote_values() {
+    assert_eq!(quote("abc"), "abc");
+    assert_eq!(quote(""), "''");
+    assert_eq!(quote("a b"), "'a b'");
+    assert_eq!(quote("it's"), "\"it's\"");
+    assert_eq!(quote("say \"hi\""), "'say \"hi\"'");
+
+    for s in &["abc", "a b", "it's", "{x,y}"] {
+        assert_eq!(unquote(&quote(s)), *s);
+    }
+}
+
+#[test]
+fn split_excludes_row_id_and_ignored() {
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE id NUMERIC\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE note STRING\n\
+         @ATTRIBUTE y {a, b}\n\
+         @DATA\n\
+         1, 0.5, 'first', a\n\
+         2, 1.5, 'second', b\n",
+    ).unwrap();
+
+    let dset = DataSet {
+        description: Some(DatasetDescription {
+            row_id_attribute: Some("id".to_owned()),
+            ignore_attributes: vec!["note".to_owned()],
+            ..Default::default()
+        }),
+        features: vec![],
+        arff,
+        target: Some("y".to_owned()),
+        sparse: None,
+    };
+
+    assert_eq!(dset.excluded_columns(), vec!["id", "note"]);
+
+    let (x, y) = dset.clone_split().unwrap();
+    assert_eq!(x.col_names().collect::<Vec<_>>(), vec!["x"]);
+    assert_eq!(y.col_names().collect::<Vec<_>>(), vec!["y"]);
+
+    let excluded = dset.clone_excluded();
+    assert_eq!(excluded.col_names().collect::<Vec<_>>(), vec!["id", "note"]);
+}
+
+#[test]
+fn mask_rows_with_missing_features() {
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE a NUMERIC\n\
+         @ATTRIBUTE b NUMERIC\n\
+         @ATTRIBUTE y NUMERIC\n\
+         @DATA\n\
+         1, ?, 1\n\
+         2, 3, ?\n\
+         ?, 4, 3\n\
+         5, 6, 4\n",
+    ).unwrap();
+
+    let dset = DataSet::from_arff(arff, Some("y"));
+
+    assert_eq!(dset.missing_mask(), vec![true, false, true, false]);
+}
+
+#[test]
+fn column_types_and_levels() {
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE kind {a, b}\n\
+         @ATTRIBUTE day STRING\n\
+         @ATTRIBUTE note STRING\n\
+         @ATTRIBUTE y {'neg', \"it's\"}\n\
+         @DATA\n\
+         ?, ?, '2018-01-01', ?, \"it's\"\n",
+    ).unwrap();
+
+    let mut dset = DataSet::from_arff(arff, Some("y"));
+    let types = |dset: &DataSet| -> Vec<_> {
+        (0..5).map(|c| dset.column_type(dset.data().col(c))).collect()
+    };
+
+    // without feature information, types and levels are determined from the values
+    assert_eq!(
+        types(&dset),
+        vec![
+            FeatureType::Numeric,
+            FeatureType::Numeric,
+            FeatureType::String,
+            FeatureType::Numeric,
+            FeatureType::Nominal,
+        ]
+    );
+    assert_eq!(dset.nominal_levels(dset.data().col(1)), None);
+    assert_eq!(dset.target_categories(), Some(vec!["neg".to_owned(), "it's".to_owned()]));
+
+    dset.features = vec![
+        test_feature("x", FeatureType::Numeric, &[]),
+        test_feature("kind", FeatureType::Nominal, &["a", "b"]),
+        test_feature("day", FeatureType::Date, &[]),
+        test_feature("note", FeatureType::String, &[]),
+        test_feature("y", FeatureType::Nominal, &["neg", "it's"]),
+    ];
+    assert_eq!(
+        types(&dset),
+        vec![
+            FeatureType::Numeric,
+            FeatureType::Nominal,
+            FeatureType::Date,
+            FeatureType::String,
+            FeatureType::Nominal,
+        ]
+    );
+    assert_eq!(
+        dset.nominal_levels(dset.data().col(1)),
+        Some(vec!["a".to_owned(), "b".to_owned()])
+    );
+    assert_eq!(dset.with_target("x").target_categories(), None);
+}
+
+#[test]
+fn mask_rows_with_missing_sparse_features() {
+    use sparse;
+
+    let (arff, features) = sparse::parse(
+        "@RELATION test\n\
+         @ATTRIBUTE w NUMERIC\n\
+         @ATTRIBUTE y NUMERIC\n\
+         @DATA\n\
+         {0 1, 1 2}\n\
+         {0 ?}\n\
+         {1 ?}\n",
+        &["y"],
+    ).unwrap();
+
+    let mut dset = DataSet::from_arff(arff, Some("y"));
+    dset.sparse = Some(features);
+
+    assert_eq!(dset.missing_mask(), vec![false, true, false]);
 }
@@ -0,0 +1,188 @@
+//! Numeric encoding of data sets with mixed nominal and numeric features
+
+use std::f64;
+
+use arff::dynamic::Value;
+
+use dataset::{DataSet, FeatureType};
+
+/// How nominal features are encoded as numbers
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NominalEncoding {
+    /// one column per level, containing 1.0 for the row's level and 0.0 otherwise
+    OneHot,
+
+    /// a single column containing the index of the level
+    Ordinal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ColumnEncoding {
+    Numeric { name: String },
+    Ordinal { name: String },
+    OneHot { name: String, levels: Vec<String> },
+}
+
+/// Encodes the features of a data set as rows of `f64`
+///
+/// Levels of nominal features are taken from the ARFF declaration rather than from the values
+/// that actually occur, so the encoding does not depend on the rows it is fitted to. Missing
+/// values are encoded as `NaN`, and string features are dropped.
+/// ```no_run
+/// # use openml::{Encoder, NominalEncoding, SupervisedClassification};
+/// let task = SupervisedClassification::from_openml(31).unwrap();
+/// let encoder = Encoder::fit(task.source_data(), NominalEncoding::OneHot);
+/// println!("{:?}", encoder.column_names());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Encoder {
+    columns: Vec<ColumnEncoding>,
+}
+
+impl Encoder {
+    /// fit encoder to the features of a data set; the target and the columns listed by
+    /// `DataSet::excluded_columns` are not encoded.
+    pub fn fit(data: &DataSet, encoding: NominalEncoding) -> Self {
+        let arff = data.data();
+        let excluded = data.excluded_columns();
+
+        let columns = (0..arff.n_cols())
+            .map(|c| arff.col(c))
+            .filter(|col| Some(col.name()) != data.target() && !excluded.contains(&col.name()))
+            .filter_map(|col| {
+                let name = col.name().to_owned();
+                match (data.column_type(col), encoding) {
+                    (FeatureType::String, _) | (FeatureType::Date, _) => None,
+                    (FeatureType::Nominal, NominalEncoding::Ordinal) => {
+                        Some(ColumnEncoding::Ordinal { name })
+                    }
+                    (FeatureType::Nominal, NominalEncoding::OneHot) => {
+                        let levels = data.nominal_levels(col).unwrap_or_default();
+                        Some(ColumnEncoding::OneHot { name, levels })
+                    }
+                    (FeatureType::Numeric, _) => Some(ColumnEncoding::Numeric { name }),
+                }
+            })
+            .collect();
+
+        Encoder { columns }
+    }
+
+    /// number of encoded columns
+    pub fn n_cols(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|enc| match *enc {
+                ColumnEncoding::OneHot { ref levels, .. } => levels.len(),
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// names of the encoded columns. One-hot encoded columns are named `feature=level`.
+    pub fn column_names(&self) -> Vec<String> {
+        let mut names = Vec::with_capacity(self.n_cols());
+        for enc in &self.columns {
+            match *enc {
+                ColumnEncoding::Numeric { ref name } | ColumnEncoding::Ordinal { ref name } => {
+                    names.push(name.clone())
+                }
+                ColumnEncoding::OneHot {
+                    ref name,
+                    ref levels,
+                } => names.extend(levels.iter().map(|level| format!("{}={}", name, level))),
+            }
+        }
+        names
+    }
+
+    /// encode a data set. Returns the rows concatenated into a single vector, where each row
+    /// consists of `n_cols` values.
+    pub fn transform(&self, data: &DataSet) -> Vec<f64> {
+        let arff = data.data();
+        let n_cols = self.n_cols();
+        let mut x = vec![0.0; arff.n_rows() * n_cols];
+
+        let mut offset = 0;
+        for enc in &self.columns {
+            match *enc {
+                ColumnEncoding::Numeric { ref name } | ColumnEncoding::Ordinal { ref name } => {
+                    let col = arff.col_by_name(name);
+                    for i in 0..arff.n_rows() {
+                        x[i * n_cols + offset] = col.item(i).as_f64().unwrap_or(f64::NAN);
+                    }
+                    offset += 1;
+                }
+                ColumnEncoding::OneHot {
+                    ref name,
+                    ref levels,
+                } => {
+                    let col = arff.col_by_name(name);
+                    for i in 0..arff.n_rows() {
+                        let row = &mut x[i * n_cols + offset..i * n_cols + offset + levels.len()];
+                        match col.item(i) {
+                            Value::Nominal(level, _) => row[level] = 1.0,
+                            _ => row.iter_mut().for_each(|v| *v = f64::NAN),
+                        }
+                    }
+                    offset += levels.len();
+                }
+            }
+        }
+
+        x
+    }
+}
+
+#[test]
+fn encode_mixed_features() {
+    use arff::dynamic::DataSet as ArffDataSet;
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE color {red, green, blue}\n\
+         @ATTRIBUTE note STRING\n\
+         @ATTRIBUTE y {a, b}\n\
+         @DATA\n\
+         1.5, green, 'one', a\n\
+         ?, ?, 'two', b\n",
+    ).unwrap();
+    let data = DataSet::from_arff(arff, Some("y"));
+
+    let onehot = Encoder::fit(&data, NominalEncoding::OneHot);
+    assert_eq!(onehot.column_names(), vec!["x", "color=red", "color=green", "color=blue"]);
+
+    let x = onehot.transform(&data);
+    assert_eq!(&x[..4], &[1.5, 0.0, 1.0, 0.0]);
+    assert!(x[4..].iter().all(|v| v.is_nan()));
+
+    let ordinal = Encoder::fit(&data, NominalEncoding::Ordinal);
+    assert_eq!(ordinal.column_names(), vec!["x", "color"]);
+    assert_eq!(&ordinal.transform(&data)[..2], &[1.5, 1.0]);
+}
+
+#[test]
+fn encode_declared_levels() {
+    use arff::dynamic::DataSet as ArffDataSet;
+    use dataset::{test_feature, FeatureInfo};
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE color {red, 'dark blue'}\n\
+         @ATTRIBUTE size {s, m}\n\
+         @ATTRIBUTE y {a, b}\n\
+         @DATA\n\
+         red, ?, a\n",
+    ).unwrap();
+    let mut data = DataSet::from_arff(arff, Some("y"));
+    data.features = vec![
+        test_feature("color", FeatureType::Nominal, &["red", "dark blue"]),
+        FeatureInfo { index: 1, ..test_feature("size", FeatureType::Nominal, &["s", "m"]) },
+        FeatureInfo { index: 2, ..test_feature("y", FeatureType::Nominal, &["a", "b"]) },
+    ];
+
+    let onehot = Encoder::fit(&data, NominalEncoding::OneHot);
+    assert_eq!(onehot.column_names(), vec!["color=red", "color=dark blue", "size=s", "size=m"]);
+    assert_eq!(&onehot.transform(&data)[..2], &[1.0, 0.0]);
+}
@@ -4,6 +4,8 @@ use std::string::FromUtf8Error;
 
 use app_dirs::AppDirsError;
 use arff::Error as ArffError;
+#[cfg(feature = "arrow")]
+use arrow::error::ArrowError;
 use hyper::Error as HyperError;
 use hyper::error::UriError;
 use hyper_tls::Error as TlsError;
@@ -21,6 +23,20 @@ pub enum Error {
     JsonError(JsonError),
     ArffError(ArffError),
     AppDirsError(AppDirsError),
+    #[cfg(feature = "arrow")]
+    ArrowError(ArrowError),
+
+    /// the server rejected a request
+    ApiError(String),
+
+    /// the requested entity does not exist
+    NotFound(String),
+
+    /// a response or data file could not be parsed
+    ParseError(String),
+
+    /// the request requires an API key, but none is configured
+    MissingApiKey,
 }
 
 impl From<IoError> for Error {
@@ -65,6 +81,13 @@ impl From<ArffError> for Error {
     }
 }
 
+#[cfg(feature = "arrow")]
+impl From<ArrowError> for Error {
+    fn from(e: ArrowError) -> Self {
+        Error::ArrowError(e)
+    }
+}
+
 impl From<AppDirsError> for Error {
     fn from(e: AppDirsError) -> Self {
         match e {
@@ -0,0 +1,263 @@
+//! Export data sets to file formats used by other tools
+
+use std::io::{self, Write};
+
+use arff::dynamic::{Column, Value};
+use serde_json;
+
+use dataset::{quote, unquote, DataSet, FeatureType};
+
+/// File formats for exporting data sets
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// comma separated values with a header line; missing values are empty
+    Csv,
+
+    /// ARFF, including the declaration of nominal attributes
+    Arff,
+
+    /// one JSON object per row; missing values are `null`
+    JsonLines,
+}
+
+impl DataSet {
+    /// write all rows of the data set
+    ///
+    /// All columns in `data()` are written, including the target and excluded columns. The
+    /// feature columns of sparse data sets can not be exported.
+    /// ```no_run
+    /// # use std::fs::File;
+    /// # use openml::{DataSet, ExportFormat};
+    /// let iris = DataSet::from_openml(61).unwrap();
+    /// iris.export(ExportFormat::Csv, File::create("iris.csv").unwrap()).unwrap();
+    /// ```
+    pub fn export<W: Write>(&self, format: ExportFormat, w: W) -> io::Result<()> {
+        let rows: Vec<_> = (0..self.n_rows()).collect();
+        self.export_rows(&rows, format, w)
+    }
+
+    /// write selected rows of the data set, e.g. the training set of a fold as returned by
+    /// `SupervisedClassification::fold_rows`.
+    pub fn export_rows<W: Write>(
+        &self,
+        rows: &[usize],
+        format: ExportFormat,
+        mut w: W,
+    ) -> io::Result<()> {
+        if self.is_sparse() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "sparse data sets can not be exported",
+            ));
+        }
+
+        let arff = self.data();
+        let columns: Vec<_> = (0..arff.n_cols())
+            .map(|c| arff.col(c))
+            .map(|col| ExportColumn {
+                col,
+                kind: self.column_type(col),
+                levels: self.nominal_levels(col).unwrap_or_default(),
+            })
+            .collect();
+
+        match format {
+            ExportFormat::Csv => write_csv(&columns, rows, &mut w),
+            ExportFormat::Arff => write_arff(arff.name(), &columns, rows, &mut w),
+            ExportFormat::JsonLines => write_json_lines(&columns, rows, &mut w),
+        }
+    }
+}
+
+/// A column with its type and nominal levels
+struct ExportColumn<'a> {
+    col: &'a Column,
+    kind: FeatureType,
+    levels: Vec<String>,
+}
+
+impl<'a> ExportColumn<'a> {
+    fn name(&self) -> &str {
+        self.col.name()
+    }
+
+    fn item(&self, i: usize) -> Value {
+        self.col.item(i)
+    }
+
+    /// the unquoted level of a nominal value
+    fn level(&self, c: usize, declared: &[String]) -> String {
+        self.levels.get(c).cloned().unwrap_or_else(|| unquote(&declared[c]))
+    }
+}
+
+fn write_csv<W: Write>(columns: &[ExportColumn], rows: &[usize], w: &mut W) -> io::Result<()> {
+    let header: Vec<_> = columns.iter().map(|col| csv_quote(col.name())).collect();
+    writeln!(w, "{}", header.join(","))?;
+
+    for &i in rows {
+        let values: Vec<_> = columns
+            .iter()
+            .map(|col| match col.item(i) {
+                Value::Missing => String::new(),
+                Value::String(s) => csv_quote(s),
+                Value::Nominal(c, declared) => csv_quote(&col.level(c, declared)),
+                v => number(&v).unwrap_or_default(),
+            })
+            .collect();
+        writeln!(w, "{}", values.join(","))?;
+    }
+
+    Ok(())
+}
+
+fn write_arff<W: Write>(
+    relation: &str,
+    columns: &[ExportColumn],
+    rows: &[usize],
+    w: &mut W,
+) -> io::Result<()> {
+    writeln!(w, "@RELATION {}\n", quote(relation))?;
+
+    for col in columns {
+        let kind = match col.kind {
+            FeatureType::Nominal => {
+                let levels: Vec<_> = col.levels.iter().map(|l| quote(l)).collect();
+                format!("{{{}}}", levels.join(","))
+            }
+            FeatureType::String | FeatureType::Date => "STRING".to_owned(),
+            FeatureType::Numeric => "NUMERIC".to_owned(),
+        };
+        writeln!(w, "@ATTRIBUTE {} {}", quote(col.name()), kind)?;
+    }
+
+    writeln!(w, "\n@DATA")?;
+    for &i in rows {
+        let values: Vec<_> = columns
+            .iter()
+            .map(|col| match col.item(i) {
+                Value::Missing => "?".to_owned(),
+                Value::String(s) => quote(s),
+                Value::Nominal(c, declared) => quote(&col.level(c, declared)),
+                v => number(&v).unwrap_or_else(|| "?".to_owned()),
+            })
+            .collect();
+        writeln!(w, "{}", values.join(","))?;
+    }
+
+    Ok(())
+}
+
+/// write one JSON object per row, with keys in column order
+fn write_json_lines<W: Write>(
+    columns: &[ExportColumn],
+    rows: &[usize],
+    w: &mut W,
+) -> io::Result<()> {
+    for &i in rows {
+        let fields: Vec<_> = columns
+            .iter()
+            .map(|col| {
+                let value = match col.item(i) {
+                    Value::Nominal(c, declared) => col.level(c, declared).into(),
+                    v => json_value(v),
+                };
+                format!("{}:{}", serde_json::Value::from(col.name()), value)
+            })
+            .collect();
+        writeln!(w, "{{{}}}", fields.join(","))?;
+    }
+
+    Ok(())
+}
+
+/// format a numeric value, or `None` if the value is not a number
+fn number(v: &Value) -> Option<String> {
+    match *v {
+        Value::U8(x) => Some(x.to_string()),
+        Value::U16(x) => Some(x.to_string()),
+        Value::U32(x) => Some(x.to_string()),
+        Value::U64(x) => Some(x.to_string()),
+        Value::I8(x) => Some(x.to_string()),
+        Value::I16(x) => Some(x.to_string()),
+        Value::I32(x) => Some(x.to_string()),
+        Value::I64(x) => Some(x.to_string()),
+        Value::F64(x) => Some(x.to_string()),
+        _ => None,
+    }
+}
+
+fn json_value(v: Value) -> serde_json::Value {
+    match v {
+        Value::Missing => serde_json::Value::Null,
+        Value::U8(x) => x.into(),
+        Value::U16(x) => x.into(),
+        Value::U32(x) => x.into(),
+        Value::U64(x) => x.into(),
+        Value::I8(x) => x.into(),
+        Value::I16(x) => x.into(),
+        Value::I32(x) => x.into(),
+        Value::I64(x) => x.into(),
+        Value::F64(x) => serde_json::Number::from_f64(x)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::String(s) => s.into(),
+        Value::Nominal(c, levels) => unquote(&levels[c]).into(),
+    }
+}
+
+/// quote CSV values if necessary
+fn csv_quote(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+fn export_to_string(data: &DataSet, rows: &[usize], format: ExportFormat) -> String {
+    let mut out = Vec::new();
+    data.export_rows(rows, format, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn export_formats() {
+    use arff::dynamic::DataSet as ArffDataSet;
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE note STRING\n\
+         @ATTRIBUTE class {a, 'c d', 'e'}\n\
+         @DATA\n\
+         1.5, 'hi \"there\"', 'e'\n\
+         ?, ?, a\n\
+         3, \"it's\", a\n",
+    ).unwrap();
+    let data = DataSet::from_arff(arff, Some("class"));
+
+    assert_eq!(
+        export_to_string(&data, &[0, 1], ExportFormat::Csv),
+        "x,note,class\n\
+         1.5,\"hi \"\"there\"\"\",e\n\
+         ,,a\n"
+    );
+
+    assert_eq!(
+        export_to_string(&data, &[1, 0], ExportFormat::JsonLines),
+        "{\"x\":null,\"note\":null,\"class\":\"a\"}\n\
+         {\"x\":1.5,\"note\":\"hi \\\"there\\\"\",\"class\":\"e\"}\n"
+    );
+
+    let exported = export_to_string(&data, &[0, 1, 2], ExportFormat::Arff);
+    assert!(exported.contains("@ATTRIBUTE class {a,'c d',e}\n"));
+
+    let reloaded = ArffDataSet::from_str(&exported).unwrap();
+    assert_eq!(reloaded.n_rows(), 3);
+    assert_eq!(reloaded.item(0, 1).as_str().unwrap(), "hi \"there\"");
+    assert_eq!(reloaded.item(0, 2).as_string().unwrap(), "e");
+    assert_eq!(reloaded.item(1, 2).as_string().unwrap(), "a");
+    assert_eq!(reloaded.item(2, 1).as_str().unwrap(), "it's");
+}
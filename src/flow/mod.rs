@@ -0,0 +1,164 @@
+//! Flows describe machine learning models, so that runs can be linked to the model that produced
+//! them.
+
+mod registry;
+
+pub use self::registry::FlowRegistry;
+
+use xml::{push_element, OML_NAMESPACE};
+
+/// Description of a flow (a machine learning model or pipeline)
+///
+/// Flows are identified by their name and external version. The description is serialized to
+/// OpenML's flow XML format with `to_xml`.
+/// ```
+/// # use openml::Flow;
+/// let flow = Flow::new("mycrate.KNearestNeighbors", "mycrate==0.3.1")
+///     .with_description("k-nearest neighbors classifier")
+///     .with_parameter("k", "int", "5", "number of neighbors")
+///     .with_dependency("mycrate==0.3.1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Flow {
+    name: String,
+    external_version: String,
+    description: String,
+    parameters: Vec<FlowParameter>,
+    dependencies: Vec<String>,
+}
+
+/// A hyper-parameter of a flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowParameter {
+    pub name: String,
+    pub data_type: String,
+    pub default_value: String,
+    pub description: String,
+}
+
+/// Trait implemented by models that can describe themselves as flow
+pub trait DescribeFlow {
+    /// get flow description
+    fn describe_flow(&self) -> Flow;
+}
+
+impl Flow {
+    /// create a new flow description
+    pub fn new<S: Into<String>, T: Into<String>>(name: S, external_version: T) -> Self {
+        Flow {
+            name: name.into(),
+            external_version: external_version.into(),
+            description: String::new(),
+            parameters: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// set a human readable description
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// add a hyper-parameter
+    pub fn with_parameter<S, T, U, V>(
+        mut self,
+        name: S,
+        data_type: T,
+        default_value: U,
+        description: V,
+    ) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+        U: Into<String>,
+        V: Into<String>,
+    {
+        self.parameters.push(FlowParameter {
+            name: name.into(),
+            data_type: data_type.into(),
+            default_value: default_value.into(),
+            description: description.into(),
+        });
+        self
+    }
+
+    /// add a dependency, such as `"openml==0.1.2"`
+    pub fn with_dependency<S: Into<String>>(mut self, dependency: S) -> Self {
+        self.dependencies.push(dependency.into());
+        self
+    }
+
+    /// get flow name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// get external version
+    pub fn external_version(&self) -> &str {
+        &self.external_version
+    }
+
+    /// get description
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// get hyper-parameters
+    pub fn parameters(&self) -> &[FlowParameter] {
+        &self.parameters
+    }
+
+    /// get dependencies
+    pub fn dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    /// the flow description in OpenML's flow XML format
+    pub fn to_xml(&self) -> String {
+        let mut xml = format!("<oml:flow xmlns:oml=\"{}\">\n", OML_NAMESPACE);
+
+        push_element(&mut xml, 1, "name", &self.name);
+        push_element(&mut xml, 1, "external_version", &self.external_version);
+        push_element(&mut xml, 1, "description", &self.description);
+        push_element(&mut xml, 1, "language", "English");
+        push_element(&mut xml, 1, "dependencies", &self.dependencies.join("\n"));
+
+        for param in &self.parameters {
+            xml.push_str("  <oml:parameter>\n");
+            push_element(&mut xml, 2, "name", &param.name);
+            push_element(&mut xml, 2, "data_type", &param.data_type);
+            push_element(&mut xml, 2, "default_value", &param.default_value);
+            push_element(&mut xml, 2, "description", &param.description);
+            xml.push_str("  </oml:parameter>\n");
+        }
+
+        xml.push_str("</oml:flow>\n");
+        xml
+    }
+}
+
+#[test]
+fn flow_xml() {
+    let flow = Flow::new("test.Flow", "test==1.0")
+        .with_description("a <test> flow")
+        .with_parameter("k", "int", "5", "neighbors")
+        .with_dependency("test==1.0");
+
+    assert_eq!(
+        flow.to_xml(),
+        "<oml:flow xmlns:oml=\"http://openml.org/openml\">\n\
+         \x20 <oml:name>test.Flow</oml:name>\n\
+         \x20 <oml:external_version>test==1.0</oml:external_version>\n\
+         \x20 <oml:description>a &lt;test&gt; flow</oml:description>\n\
+         \x20 <oml:language>English</oml:language>\n\
+         \x20 <oml:dependencies>test==1.0</oml:dependencies>\n\
+         \x20 <oml:parameter>\n\
+         \x20   <oml:name>k</oml:name>\n\
+         \x20   <oml:data_type>int</oml:data_type>\n\
+         \x20   <oml:default_value>5</oml:default_value>\n\
+         \x20   <oml:description>neighbors</oml:description>\n\
+         \x20 </oml:parameter>\n\
+         </oml:flow>\n"
+    );
+}
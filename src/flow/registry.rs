@@ -0,0 +1,129 @@
+//! Local registry of flow descriptions
+
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use app_dirs::{app_root, AppDataType};
+use serde_json;
+
+use error::Result;
+use openml_api::file_lock::{ExclusiveLock, SharedLock};
+use openml_api::APP_INFO;
+
+use super::Flow;
+
+/// A local registry that assigns stable IDs to flow descriptions
+///
+/// Registering the same flow description twice yields the same ID, while any change to the
+/// description (e.g. a new version or a different default parameter) yields a new ID. The
+/// registry is stored as a JSON file, so IDs persist across program runs. The IDs are local and
+/// unrelated to flow IDs on the OpenML server.
+#[derive(Debug, Clone)]
+pub struct FlowRegistry {
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    id: u32,
+    flow: Flow,
+}
+
+impl FlowRegistry {
+    /// open registry in the user's data directory
+    pub fn new() -> Result<Self> {
+        let mut path = app_root(AppDataType::UserData, &APP_INFO)?;
+        path.push("flows.json");
+        Ok(FlowRegistry { path })
+    }
+
+    /// open registry stored in the given file
+    pub fn with_file<P: AsRef<Path>>(path: P) -> Self {
+        FlowRegistry {
+            path: path.as_ref().to_owned(),
+        }
+    }
+
+    /// get the ID of a flow, registering it if it is not known yet
+    pub fn register(&self, flow: &Flow) -> Result<u32> {
+        // existing entries are read first; the file is truncated before they are written back
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        let mut file = ExclusiveLock::new(file)?;
+
+        let mut data = String::new();
+        file.read_to_string(&mut data)?;
+
+        let mut entries: Vec<Entry> = if data.trim().is_empty() {
+            Vec::new()
+        } else {
+            serde_json::from_str(&data)?
+        };
+
+        if let Some(entry) = entries.iter().find(|e| &e.flow == flow) {
+            return Ok(entry.id);
+        }
+
+        let id = entries.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+        entries.push(Entry {
+            id,
+            flow: flow.clone(),
+        });
+
+        file.truncate()?;
+        file.write_all(serde_json::to_string_pretty(&entries)?.as_bytes())?;
+
+        Ok(id)
+    }
+
+    /// get the flow registered with `id`
+    pub fn get(&self, id: u32) -> Result<Option<Flow>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let file = OpenOptions::new().read(true).open(&self.path)?;
+        let mut file = SharedLock::new(file)?;
+
+        let mut data = String::new();
+        file.read_to_string(&mut data)?;
+
+        if data.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let entries: Vec<Entry> = serde_json::from_str(&data)?;
+        Ok(entries.into_iter().find(|e| e.id == id).map(|e| e.flow))
+    }
+}
+
+#[test]
+fn stable_ids() {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    let path = env::temp_dir().join(format!("openml-flow-registry-{}.json", process::id()));
+    let _ = fs::remove_file(&path);
+
+    let registry = FlowRegistry::with_file(&path);
+
+    let a = Flow::new("test.A", "1.0");
+    let b = Flow::new("test.A", "1.1");
+
+    let id_a = registry.register(&a).unwrap();
+    let id_b = registry.register(&b).unwrap();
+
+    assert_ne!(id_a, id_b);
+    assert_eq!(registry.register(&a.clone()).unwrap(), id_a);
+    assert_eq!(FlowRegistry::with_file(&path).register(&b).unwrap(), id_b);
+    assert_eq!(registry.get(id_b).unwrap(), Some(b));
+    assert_eq!(registry.get(42).unwrap(), None);
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,116 @@
+//! Traits for models that are trained and evaluated fold by fold
+
+/// A classification model with features of type `X` and classes of type `C`
+///
+/// Tasks evaluate classifiers with `SupervisedClassification::evaluate`:
+/// ```no_run
+/// # use openml::prelude::*;
+/// # use openml::{PredictiveAccuracy, SupervisedClassification};
+/// # use openml::baseline::NaiveBayesClassifier;
+/// let task = SupervisedClassification::from_openml(59).unwrap();
+/// let mut nbc = NaiveBayesClassifier::<u8>::new();
+/// let result: PredictiveAccuracy<_> = task.evaluate(&mut nbc);
+/// ```
+pub trait Classifier<X, C> {
+    /// train the model on the feature rows `x` and their classes `y`. The model is trained once
+    /// per fold, so training should replace the results of any previous training.
+    fn fit(&mut self, x: &[&[X]], y: &[&C]);
+
+    /// predict the class of a single feature row
+    fn predict(&self, x: &[X]) -> C;
+}
+
+/// A regression model with features of type `X`
+///
+/// Tasks evaluate regressors with `SupervisedRegression::evaluate`.
+pub trait Regressor<X> {
+    /// train the model on the feature rows `x` and their target values `y`. The model is trained
+    /// once per fold, so training should replace the results of any previous training.
+    fn fit(&mut self, x: &[&[X]], y: &[&f64]);
+
+    /// predict the target value of a single feature row
+    fn predict(&self, x: &[X]) -> f64;
+}
+
+#[cfg(test)]
+fn test_procedure() -> Box<::procedures::Procedure> {
+    use procedures::{Fold, FrozenSets};
+
+    let mut fold0 = Fold::new(0, 0);
+    fold0.trainset = vec![0, 1, 3, 4];
+    fold0.testset = vec![2, 5];
+
+    let mut fold1 = Fold::new(0, 1);
+    fold1.trainset = vec![1, 2, 4, 5];
+    fold1.testset = vec![0, 3];
+
+    Box::new(FrozenSets {
+        folds: vec![vec![fold0, fold1]],
+    })
+}
+
+#[test]
+fn evaluate_classifier() {
+    use arff::dynamic::DataSet as ArffDataSet;
+    use baseline::NaiveBayesClassifier;
+    use dataset::DataSet;
+    use measure_accumulator::{MeasureAccumulator, PredictiveAccuracy};
+    use tasks::SupervisedClassification;
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE class {a, b}\n\
+         @DATA\n\
+         1.0, a\n\
+         1.2, a\n\
+         0.8, a\n\
+         5.0, b\n\
+         5.2, b\n\
+         4.8, b\n",
+    ).unwrap();
+
+    let task = SupervisedClassification {
+        id: "1".to_owned(),
+        name: "test".to_owned(),
+        source_data: DataSet::from_arff(arff, Some("class")),
+        estimation_procedure: test_procedure(),
+    };
+
+    let mut nbc = NaiveBayesClassifier::<u8>::new();
+    let accuracy: PredictiveAccuracy<_> = task.evaluate(&mut nbc);
+    assert_eq!(accuracy.result(), 1.0);
+}
+
+#[test]
+fn evaluate_regressor() {
+    use arff::dynamic::DataSet as ArffDataSet;
+    use baseline::NaiveLinearRegression;
+    use dataset::DataSet;
+    use measure_accumulator::{MeasureAccumulator, RootMeanSquaredError};
+    use tasks::SupervisedRegression;
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE y NUMERIC\n\
+         @DATA\n\
+         1, 3\n\
+         2, 5\n\
+         3, 7\n\
+         4, 9\n\
+         5, 11\n\
+         6, 13\n",
+    ).unwrap();
+
+    let task = SupervisedRegression {
+        id: "2".to_owned(),
+        name: "test".to_owned(),
+        source_data: DataSet::from_arff(arff, Some("y")),
+        estimation_procedure: test_procedure(),
+    };
+
+    let mut nlr = NaiveLinearRegression::new();
+    let rmse: RootMeanSquaredError<_> = task.evaluate(&mut nlr);
+    assert!(rmse.result() < 1e-9);
+}
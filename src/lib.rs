@@ -39,13 +39,28 @@
 
 extern crate app_dirs;
 extern crate arff;
+#[cfg(feature = "arrow")]
+extern crate arrow;
+#[cfg(feature = "parquet")]
+extern crate bytes;
 extern crate fs2;
 extern crate futures;
 extern crate hyper;
 extern crate hyper_tls;
 #[macro_use]
+extern crate lazy_static;
+#[macro_use]
 extern crate log;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
 extern crate num_traits;
+#[cfg(feature = "parquet")]
+extern crate parquet;
+#[cfg(test)]
+#[macro_use]
+extern crate quickcheck;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
@@ -56,25 +71,89 @@ extern crate simple_logger;
 extern crate time;
 extern crate tokio_core;
 
+#[cfg(feature = "arrow")]
+mod arrow_support;
 pub mod baseline;
 mod dataset;
+mod encoding;
 mod error;
+mod export;
+mod flow;
+mod learner;
 mod measure_accumulator;
+#[cfg(feature = "ndarray")]
+mod ndarray_support;
 mod openml_api;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "parquet")]
+mod parquet_data;
+mod predictions;
 pub mod prelude;
 mod procedures;
+pub mod qualities;
+mod run;
+mod sparse;
+mod study;
 mod tasks;
+mod xml;
+
+#[cfg(feature = "arrow")]
+pub use arrow_support::ArrowColumn;
+
+pub use dataset::{DataSet, DatasetDescription, DatasetStatus, FeatureInfo, FeatureType};
+
+/// Alias of `DataSet`, following the spelling of `DatasetFilter` and `DatasetSummary`
+pub use dataset::DataSet as Dataset;
+
+pub use encoding::{Encoder, NominalEncoding};
+
+pub use export::ExportFormat;
+
+pub use flow::{DescribeFlow, Flow, FlowParameter, FlowRegistry};
+
+pub use learner::{Classifier, Regressor};
 
 pub use measure_accumulator::{
+    AdjustedRandIndex,
     MeasureAccumulator,
+    MergeableAccumulator,
     PredictiveAccuracy,
     RootMeanSquaredError
 };
 
+pub use openml_api::{
+    list_datasets,
+    list_qualities,
+    list_tasks,
+    Config,
+    DatasetFilter,
+    DatasetSummary,
+    FromOpenml,
+    TaskFilter,
+    TaskSummary
+};
+
+pub use predictions::{Prediction, PredictionValue, Predictions};
+
+pub use qualities::Qualities;
+
+pub use run::Run;
+
+pub use sparse::SparseFeatures;
+
+pub use study::{BenchmarkSuite, Study};
+
 pub use tasks::{
+    FoldError,
+    FoldFailure,
+    MissingMask,
+    RunReport,
     SupervisedClassification,
     SupervisedRegression,
-    Task
+    Task,
+    TaskStatus,
+    TaskType
 };
 
 #[cfg(test)]
@@ -27,6 +27,13 @@ pub trait MeasureAccumulator<T> {
     }
 }
 
+/// Trait implemented by performance measures that can be computed in parts, e.g. one per fold
+pub trait MergeableAccumulator<T>: MeasureAccumulator<T> {
+    /// combine with a measure that was updated with other predictions, e.g. those of another
+    /// fold. The result is the same as if all predictions were passed to a single measure.
+    fn merge(&mut self, other: Self);
+}
+
 /// Classification Accuracy: relative amount of correctly classified labels
 #[derive(Debug)]
 pub struct PredictiveAccuracy<T> {
@@ -60,6 +67,16 @@ where
     }
 }
 
+impl<T> MergeableAccumulator<T> for PredictiveAccuracy<T>
+where
+    T: PartialEq,
+{
+    fn merge(&mut self, other: Self) {
+        self.n_correct += other.n_correct;
+        self.n_wrong += other.n_wrong;
+    }
+}
+
 /// Root Mean Squared Error
 #[derive(Debug)]
 pub struct RootMeanSquaredError<T> {
@@ -91,6 +108,16 @@ where
     }
 }
 
+impl<T> MergeableAccumulator<T> for RootMeanSquaredError<T>
+where
+    T: AsPrimitive<f64>,
+{
+    fn merge(&mut self, other: Self) {
+        self.sum_of_squares += other.sum_of_squares;
+        self.n += other.n;
+    }
+}
+
 
 /// Adjusted Rand Index
 #[derive(Debug)]
@@ -143,6 +170,16 @@ impl<T> MeasureAccumulator<T> for AdjustedRandIndex<T>
     }
 }
 
+impl<T> MergeableAccumulator<T> for AdjustedRandIndex<T>
+    where T: Eq + Hash + Clone,
+{
+    fn merge(&mut self, other: Self) {
+        for (pair, n) in other.contingency_table {
+            *self.contingency_table.entry(pair).or_insert(0) += n;
+        }
+    }
+}
+
 fn combinations(n: usize) -> usize {
     if n % 2 == 0 {
         (n - 1) * (n / 2)
@@ -161,3 +198,43 @@ fn ari() {
 
     assert_eq!(ari.result(), 0.24242424242424246);
 }
+
+#[cfg(test)]
+fn split_update<M: MergeableAccumulator<T>, T>(pairs: &[(T, T)], at: usize) -> (M, M) {
+    let at = if pairs.is_empty() { 0 } else { at % (pairs.len() + 1) };
+
+    let mut single = M::new();
+    for (k, p) in pairs {
+        single.update_one(k, p);
+    }
+
+    let mut first = M::new();
+    let mut second = M::new();
+    for (k, p) in &pairs[..at] {
+        first.update_one(k, p);
+    }
+    for (k, p) in &pairs[at..] {
+        second.update_one(k, p);
+    }
+    first.merge(second);
+
+    (single, first)
+}
+
+#[cfg(test)]
+quickcheck! {
+    fn merge_accuracy(pairs: Vec<(u8, u8)>, at: usize) -> bool {
+        let (single, merged) = split_update::<PredictiveAccuracy<_>, _>(&pairs, at);
+        single.n_correct == merged.n_correct && single.n_wrong == merged.n_wrong
+    }
+
+    fn merge_rmse(pairs: Vec<(i16, i16)>, at: usize) -> bool {
+        let (single, merged) = split_update::<RootMeanSquaredError<_>, _>(&pairs, at);
+        single.sum_of_squares == merged.sum_of_squares && single.n == merged.n
+    }
+
+    fn merge_ari(pairs: Vec<(u8, u8)>, at: usize) -> bool {
+        let (single, merged) = split_update::<AdjustedRandIndex<_>, _>(&pairs, at);
+        single.contingency_table == merged.contingency_table
+    }
+}
@@ -0,0 +1,147 @@
+//! Conversion of data sets to `ndarray` arrays, and runners that pass arrays to flows
+
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use serde::de::DeserializeOwned;
+
+use dataset::DataSet;
+use encoding::{Encoder, NominalEncoding};
+use measure_accumulator::MeasureAccumulator;
+use procedures::{Fold, Procedure};
+use tasks::{SupervisedClassification, SupervisedRegression};
+use tasks::runner::{load_y, require_dense};
+
+impl DataSet {
+    /// get the features as matrix with one row per instance. Nominal features are encoded with
+    /// `Encoder`, and missing values are `NaN`.
+    pub fn features_array(&self, encoding: NominalEncoding) -> Array2<f64> {
+        require_dense(self);
+        let encoder = Encoder::fit(self, encoding);
+        Array2::from_shape_vec((self.n_rows(), encoder.n_cols()), encoder.transform(self))
+            .expect("encoded features do not match the number of rows")
+    }
+
+    /// get the target column. Panics if there is no target or the target has missing values.
+    pub fn target_array<Y: DeserializeOwned>(&self) -> Array1<Y> {
+        Array1::from(load_y(self, "target_array"))
+    }
+}
+
+impl SupervisedClassification {
+    /// run task, passing the training and testing partition of each fold as arrays. The flow
+    /// receives the training features, the training targets and the testing features, and
+    /// returns one prediction per testing row.
+    pub fn run_ndarray<Y, F, M>(&self, encoding: NominalEncoding, flow: F) -> M
+    where
+        F: Fn(ArrayView2<f64>, ArrayView1<Y>, ArrayView2<f64>) -> Array1<Y>,
+        Y: DeserializeOwned + Clone,
+        M: MeasureAccumulator<Y>,
+    {
+        let x = self.source_data.features_array(encoding);
+        let y = self.source_data.target_array();
+
+        let mut measure = M::new();
+        run_folds_ndarray(&*self.estimation_procedure, &x, &y, flow, |_, known, pred| {
+            measure.update_one(known, &pred)
+        });
+        measure
+    }
+}
+
+impl SupervisedRegression {
+    /// run task, passing the training and testing partition of each fold as arrays. The flow
+    /// receives the training features, the training targets and the testing features, and
+    /// returns one prediction per testing row.
+    pub fn run_ndarray<Y, F, M>(&self, encoding: NominalEncoding, flow: F) -> M
+    where
+        F: Fn(ArrayView2<f64>, ArrayView1<Y>, ArrayView2<f64>) -> Array1<Y>,
+        Y: DeserializeOwned + Clone,
+        M: MeasureAccumulator<Y>,
+    {
+        let x = self.source_data.features_array(encoding);
+        let y = self.source_data.target_array();
+
+        let mut measure = M::new();
+        run_folds_ndarray(&*self.estimation_procedure, &x, &y, flow, |_, known, pred| {
+            measure.update_one(known, &pred)
+        });
+        measure
+    }
+}
+
+/// Array counterpart of `run_folds`: select the rows of each fold and pass them to the flow.
+fn run_folds_ndarray<Y, F, P>(
+    procedure: &Procedure,
+    x: &Array2<f64>,
+    y: &Array1<Y>,
+    flow: F,
+    mut on_prediction: P,
+) where
+    Y: Clone,
+    F: Fn(ArrayView2<f64>, ArrayView1<Y>, ArrayView2<f64>) -> Array1<Y>,
+    P: FnMut(&Fold, &Y, Y),
+{
+    for fold in procedure.iter() {
+        let x_train = x.select(Axis(0), &fold.trainset);
+        let y_train = y.select(Axis(0), &fold.trainset);
+        let x_test = x.select(Axis(0), &fold.testset);
+
+        let predictions = flow(x_train.view(), y_train.view(), x_test.view());
+        assert_eq!(
+            predictions.len(),
+            fold.testset.len(),
+            "the flow must return one prediction per testing row"
+        );
+
+        for (&i, pred) in fold.testset.iter().zip(predictions) {
+            on_prediction(fold, &y[i], pred);
+        }
+    }
+}
+
+#[test]
+fn arrays_and_folds() {
+    use arff::dynamic::DataSet as ArffDataSet;
+    use procedures::FrozenSets;
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE c {u, v}\n\
+         @ATTRIBUTE y NUMERIC\n\
+         @DATA\n\
+         1, u, 10\n\
+         2, v, 20\n\
+         3, ?, 30\n",
+    ).unwrap();
+    let data = DataSet::from_arff(arff, Some("y"));
+
+    let x = data.features_array(NominalEncoding::Ordinal);
+    let y: Array1<f64> = data.target_array();
+
+    assert_eq!(x.shape(), &[3, 2]);
+    assert_eq!(x[[1, 1]], 1.0);
+    assert!(x[[2, 1]].is_nan());
+    assert_eq!(y, Array1::from(vec![10.0, 20.0, 30.0]));
+
+    let mut fold = Fold::new(0, 0);
+    fold.trainset = vec![0, 2];
+    fold.testset = vec![1];
+    let procedure = FrozenSets {
+        folds: vec![vec![fold]],
+    };
+
+    let mut results = vec![];
+    run_folds_ndarray(
+        &procedure,
+        &x,
+        &y,
+        |x_train, y_train, x_test| {
+            assert_eq!(x_train.column(0).to_vec(), vec![1.0, 3.0]);
+            assert_eq!(y_train.to_vec(), vec![10.0, 30.0]);
+            x_test.column(0).map(|x| x * 10.0)
+        },
+        |_, known, pred| results.push((*known, pred)),
+    );
+
+    assert_eq!(results, vec![(20.0, 20.0)]);
+}
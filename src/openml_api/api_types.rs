@@ -1,5 +1,7 @@
 use serde_json;
 
+use qualities::Qualities;
+
 /// Generic JSON response as returned by the OpenML API
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GenericResponse(serde_json::Value);
@@ -11,6 +13,57 @@ impl GenericResponse {
     }
 }
 
+/// interpret a JSON value as string; numbers are converted
+pub(crate) fn json_string(v: &serde_json::Value) -> Option<String> {
+    match *v {
+        serde_json::Value::String(ref s) => Some(s.clone()),
+        serde_json::Value::Number(ref n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// interpret a JSON value as number; the API often returns numbers as strings
+pub(crate) fn json_f64(v: &serde_json::Value) -> Option<f64> {
+    match *v {
+        serde_json::Value::String(ref s) => s.trim().parse().ok(),
+        _ => v.as_f64(),
+    }
+}
+
+/// interpret a JSON value as integer; the API often returns numbers as strings
+pub(crate) fn json_u32(v: &serde_json::Value) -> Option<u32> {
+    match *v {
+        serde_json::Value::String(ref s) => s.trim().parse().ok(),
+        _ => v.as_u64().map(|n| n as u32),
+    }
+}
+
+/// interpret a JSON value as boolean; the API often returns booleans as strings
+pub(crate) fn json_bool(v: &serde_json::Value) -> Option<bool> {
+    match *v {
+        serde_json::Value::String(ref s) => s.trim().parse().ok(),
+        _ => v.as_bool(),
+    }
+}
+
+/// parse a list of `{"name": ..., "value": ...}` items. Items without numeric value are skipped.
+pub(crate) fn parse_qualities(v: &serde_json::Value) -> Qualities {
+    json_list(v)
+        .into_iter()
+        .filter_map(|q| Some((json_string(&q["name"])?, json_f64(&q["value"])?)))
+        .collect()
+}
+
+/// interpret a JSON value as list; single items are not always wrapped in an array by the API
+pub(crate) fn json_list(v: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match *v {
+        serde_json::Value::Array(ref items) => items.iter().collect(),
+        serde_json::Value::Null => vec![],
+        _ => vec![v],
+    }
+}
+
 /// A row in a split file
 #[derive(Debug, Deserialize)]
 pub(crate) struct CrossValItem {
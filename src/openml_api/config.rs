@@ -0,0 +1,166 @@
+//! Client configuration, compatible with the config file of the OpenML Python client
+
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use error::Result;
+
+/// Server used if none is configured
+const DEFAULT_SERVER: &str = "https://www.openml.org/api/v1/xml";
+
+lazy_static! {
+    static ref CONFIG: Config = Config::load().unwrap_or_else(|e| {
+        warn!("Could not load OpenML config file: {:?}", e);
+        Config::default()
+    });
+}
+
+/// Global configuration, loaded from `~/.openml/config` on first use
+pub(crate) fn config() -> &'static Config {
+    &CONFIG
+}
+
+/// OpenML client configuration
+///
+/// The configuration file uses the same format as the Python client, so both can share a single
+/// `~/.openml/config`:
+/// ```text
+/// apikey = 0123456789abcdef0123456789abcdef
+/// server = https://www.openml.org/api/v1/xml
+/// cachedir = ~/.openml/cache
+/// ```
+/// Unknown keys are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// API key used to authenticate requests
+    pub apikey: Option<String>,
+
+    /// base URL of the REST API
+    pub server: String,
+
+    /// directory where downloaded responses are cached
+    pub cachedir: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            apikey: None,
+            server: DEFAULT_SERVER.to_owned(),
+            cachedir: None,
+        }
+    }
+}
+
+impl Config {
+    /// load configuration from `~/.openml/config`, or use the defaults if there is no such file
+    pub fn load() -> Result<Self> {
+        match env::home_dir().map(|home| home.join(".openml").join("config")) {
+            Some(ref path) if path.is_file() => Config::from_file(path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    /// load configuration from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut content = String::new();
+        File::open(path)?.read_to_string(&mut content)?;
+        Ok(Config::parse(&content))
+    }
+
+    /// parse configuration from the content of a config file
+    pub fn parse(content: &str) -> Self {
+        let mut config = Config::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            let mut kv = line.splitn(2, '=');
+            let key = kv.next().unwrap().trim();
+            let value = match kv.next() {
+                Some(v) => v.trim(),
+                None => continue,
+            };
+
+            match key {
+                "apikey" if !value.is_empty() => config.apikey = Some(value.to_owned()),
+                "server" if !value.is_empty() => {
+                    config.server = value.trim_end_matches('/').to_owned()
+                }
+                "cachedir" if !value.is_empty() => config.cachedir = Some(expand_home(value)),
+                _ => {}
+            }
+        }
+
+        config
+    }
+
+    /// root of the REST API, without the response format (e.g. `https://www.openml.org/api/v1`)
+    pub fn api_root(&self) -> &str {
+        let server = self.server.trim_end_matches('/');
+        server
+            .strip_suffix("/xml")
+            .or_else(|| server.strip_suffix("/json"))
+            .unwrap_or(server)
+    }
+
+    /// base URL for requests to the JSON API
+    pub fn json_api(&self) -> String {
+        format!("{}/json", self.api_root())
+    }
+
+    /// base URL for requests to the XML API
+    pub fn xml_api(&self) -> String {
+        format!("{}/xml", self.api_root())
+    }
+
+    /// append the API key to a URL, if the URL points to the configured server
+    pub(crate) fn authenticate(&self, url: &str) -> String {
+        match self.apikey {
+            Some(ref key) if url.starts_with(self.api_root()) => {
+                let sep = if url.contains('?') { '&' } else { '?' };
+                format!("{}{}api_key={}", url, sep, key)
+            }
+            _ => url.to_owned(),
+        }
+    }
+}
+
+/// replace a leading `~` with the user's home directory
+fn expand_home(path: &str) -> PathBuf {
+    if path == "~" || path.starts_with("~/") {
+        if let Some(home) = env::home_dir() {
+            return home.join(path[1..].trim_start_matches('/'));
+        }
+    }
+    PathBuf::from(path)
+}
+
+#[test]
+fn parse_config() {
+    let config = Config::parse(
+        "apikey = abc123\n\
+         server = https://test.openml.org/api/v1/xml\n\
+         # comment\n\
+         avoid_duplicate_runs = True\n",
+    );
+
+    assert_eq!(config.apikey, Some("abc123".to_owned()));
+    assert_eq!(config.api_root(), "https://test.openml.org/api/v1");
+    assert_eq!(config.json_api(), "https://test.openml.org/api/v1/json");
+    assert_eq!(config.cachedir, None);
+
+    assert_eq!(
+        config.authenticate("https://test.openml.org/api/v1/json/task/59"),
+        "https://test.openml.org/api/v1/json/task/59?api_key=abc123"
+    );
+    assert_eq!(
+        config.authenticate("https://www.openml.org/data/v1/download/61"),
+        "https://www.openml.org/data/v1/download/61"
+    );
+}
@@ -1,7 +1,7 @@
 //! file locking mechanisms
 
 use std::fs::File;
-use std::io::{self, Read, Write};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 
 use fs2::FileExt;
 
@@ -16,6 +16,13 @@ impl ExclusiveLock {
         file.lock_exclusive()?;
         Ok(ExclusiveLock { file })
     }
+
+    /// discard the content of the locked file
+    pub fn truncate(&mut self) -> io::Result<()> {
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        Ok(())
+    }
 }
 
 impl Drop for ExclusiveLock {
@@ -3,13 +3,22 @@ use arff;
 use arff::dynamic::DataSet as ArffDataSet;
 use serde_json;
 
-use dataset::DataSet;
-use error::Result;
+use dataset::{DataSet, DatasetDescription, DatasetStatus, FeatureInfo, FeatureType};
+use error::{Error, Result};
+#[cfg(feature = "parquet")]
+use parquet_data;
 use procedures::{Fold, FrozenSets};
+use sparse;
+use study::Study;
 use tasks::{SupervisedClassification, SupervisedRegression};
 
-use super::api_types::{CrossValItem, GenericResponse, TrainTest};
+use super::api_types::{
+    json_bool, json_list, json_string, json_u32, CrossValItem, GenericResponse, TrainTest,
+};
+use super::{config, Id};
 use super::web_access::get_cached;
+#[cfg(feature = "parquet")]
+use super::web_access::get_cached_bytes;
 
 impl DataSet {
     fn from_json(item: &serde_json::Value) -> Self {
@@ -17,25 +26,156 @@ impl DataSet {
         let id = v["data_set_id"].as_str().unwrap();
         let target = v["target_feature"].as_str();
 
-        let info_url = format!("https://www.openml.org/api/v1/json/data/{}", id);
-        let info: GenericResponse = serde_json::from_str(&get_cached(&info_url).unwrap()).unwrap();
+        DataSet::load(id, target).unwrap()
+    }
+
+    /// load data set description and data. `target` overrides the data set's default target.
+    pub(crate) fn load<T: Id>(id: T, target: Option<&str>) -> Result<Self> {
+        let info_url = format!("{}/data/{}", config().json_api(), id.as_string());
+        let info: GenericResponse = serde_json::from_str(&get_cached(&info_url)?)?;
 
-        let default_target = info.look_up("/data_set_description/default_target_attribute")
-            .and_then(|v| v.as_str());
+        let description = match info.look_up("/data_set_description") {
+            Some(v) => DatasetDescription::from_json(v)?,
+            None => return Err(Error::NotFound(format!("data set {}", id.as_string()))),
+        };
 
-        let target = match (default_target, target) {
-            (Some(s), None) | (_, Some(s)) => Some(s.to_owned()),
+        let inactive = match description.status {
+            DatasetStatus::Active => None,
+            DatasetStatus::Deprecated => Some("is deactivated"),
+            DatasetStatus::InPreparation => Some("is still in preparation"),
+        };
+        if let Some(status) = inactive {
+            warn!(
+                "Data set {} ({}, version {}) {}",
+                description.id, description.name, description.version, status
+            );
+        }
+
+        let target = match (description.default_target_attribute.as_ref(), target) {
+            (Some(s), None) => Some(s.to_owned()),
+            (_, Some(s)) => Some(s.to_owned()),
             (None, None) => None,
         };
 
-        let dset_url = info.look_up("/data_set_description/url")
-            .unwrap()
-            .as_str()
-            .unwrap();
-        let dset_str = get_cached(&dset_url).unwrap();
-        let dset = ArffDataSet::from_str(&dset_str).unwrap();
+        let features_url = format!("{}/data/features/{}", config().json_api(), description.id);
+        let features: GenericResponse = serde_json::from_str(&get_cached(&features_url)?)?;
+        let features = match features.look_up("/data_features/feature") {
+            Some(f) => json_list(f)
+                .into_iter()
+                .map(FeatureInfo::from_json)
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+
+        let (dset, sparse) = if description.format.eq_ignore_ascii_case("sparse_arff") {
+            let dense: Vec<&str> = description
+                .row_id_attribute
+                .iter()
+                .chain(description.ignore_attributes.iter())
+                .chain(target.iter())
+                .map(String::as_str)
+                .collect();
+            let (dset, sparse) = sparse::parse(&get_cached(&description.url)?, &dense)?;
+            (dset, Some(sparse))
+        } else if let Some(dset) = load_parquet(&description, &features) {
+            (dset, None)
+        } else {
+            (ArffDataSet::from_str(&get_cached(&description.url)?)?, None)
+        };
+
+        Ok(DataSet {
+            description: Some(description),
+            features,
+            arff: dset,
+            target,
+            sparse,
+        })
+    }
+}
+
+/// load a data set from its parquet file, if available
+#[cfg(feature = "parquet")]
+fn load_parquet(
+    description: &DatasetDescription,
+    features: &[FeatureInfo],
+) -> Option<ArffDataSet> {
+    let url = description.parquet_url.as_ref()?;
+    let parsed = get_cached_bytes(url)
+        .and_then(|data| parquet_data::parse(data, &description.name, features));
+
+    match parsed {
+        Ok(dset) => Some(dset),
+        Err(e) => {
+            warn!("Could not load parquet file, falling back to ARFF: {:?}", e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+fn load_parquet(_: &DatasetDescription, _: &[FeatureInfo]) -> Option<ArffDataSet> {
+    None
+}
+
+impl DatasetDescription {
+    fn from_json(v: &serde_json::Value) -> Result<Self> {
+        let string_list = |v: &serde_json::Value| -> Vec<String> {
+            json_list(v).into_iter().filter_map(json_string).collect()
+        };
+
+        let id = json_u32(&v["id"])
+            .ok_or_else(|| Error::ParseError("data set description without ID".to_owned()))?;
+        let url = json_string(&v["url"])
+            .ok_or_else(|| Error::ParseError(format!("data set description {} without URL", id)))?;
+
+        Ok(DatasetDescription {
+            id,
+            name: json_string(&v["name"]).unwrap_or_default(),
+            version: json_u32(&v["version"]).unwrap_or(1),
+            description: json_string(&v["description"]).unwrap_or_default(),
+            format: json_string(&v["format"]).unwrap_or_default(),
+            creators: string_list(&v["creator"]),
+            contributors: string_list(&v["contributor"]),
+            collection_date: json_string(&v["collection_date"]),
+            upload_date: json_string(&v["upload_date"]),
+            licence: json_string(&v["licence"]),
+            citation: json_string(&v["citation"]),
+            original_data_url: json_string(&v["original_data_url"]),
+            status: json_string(&v["status"])
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DatasetStatus::Active),
+            default_target_attribute: json_string(&v["default_target_attribute"]),
+            row_id_attribute: json_string(&v["row_id_attribute"]),
+            ignore_attributes: string_list(&v["ignore_attribute"]),
+            tags: string_list(&v["tag"]),
+            url,
+            parquet_url: json_string(&v["parquet_url"]),
+        })
+    }
+}
+
+impl FeatureInfo {
+    fn from_json(v: &serde_json::Value) -> Result<Self> {
+        let flag = |key: &str| json_bool(&v[key]).unwrap_or(false);
 
-        DataSet { arff: dset, target }
+        let index = json_u32(&v["index"])
+            .ok_or_else(|| Error::ParseError("feature without index".to_owned()))?;
+
+        Ok(FeatureInfo {
+            index: index as usize,
+            name: json_string(&v["name"]).unwrap_or_default(),
+            data_type: json_string(&v["data_type"])
+                .and_then(|t| t.parse().ok())
+                .unwrap_or(FeatureType::String),
+            nominal_values: json_list(&v["nominal_value"])
+                .into_iter()
+                .filter_map(json_string)
+                .collect(),
+            is_target: flag("is_target"),
+            is_ignore: flag("is_ignore"),
+            is_row_identifier: flag("is_row_identifier"),
+            number_of_missing_values: json_u32(&v["number_of_missing_values"]).unwrap_or(0) as u64,
+        })
     }
 }
 
@@ -92,6 +232,28 @@ impl SupervisedRegression {
     }
 }
 
+impl Study {
+    pub(crate) fn from_json(study_json: &serde_json::Value) -> Result<Self> {
+        let id = json_u32(&study_json["id"])
+            .ok_or_else(|| Error::ParseError("study without ID".to_owned()))?;
+
+        Ok(Study {
+            id,
+            alias: json_string(&study_json["alias"]),
+            name: json_string(&study_json["name"]).unwrap_or_default(),
+            description: json_string(&study_json["description"]).unwrap_or_default(),
+            task_ids: json_list(&study_json["tasks"]["task_id"])
+                .into_iter()
+                .filter_map(json_u32)
+                .collect(),
+            dataset_ids: json_list(&study_json["data"]["data_id"])
+                .into_iter()
+                .filter_map(json_u32)
+                .collect(),
+        })
+    }
+}
+
 impl FrozenSets {
     fn from_json(item: &serde_json::Value) -> Self {
         let v = &item["estimation_procedure"];
@@ -115,8 +277,9 @@ impl FrozenSets {
             }
             let mut rep = &mut folds[item.repeat];
 
-            if item.fold >= rep.len() {
-                rep.resize(item.fold + 1, Fold::new());
+            while item.fold >= rep.len() {
+                let n = rep.len();
+                rep.push(Fold::new(item.repeat, n));
             }
             let mut fold = &mut rep[item.fold];
 
@@ -129,3 +292,84 @@ impl FrozenSets {
         Ok(FrozenSets { folds })
     }
 }
+
+#[test]
+fn study_from_json() {
+    let study: serde_json::Value = serde_json::from_str(
+        r#"{"id": "99", "alias": "OpenML-CC18", "main_entity_type": "task",
+            "name": "OpenML-CC18 Curated Classification benchmark",
+            "description": "curated suite",
+            "data": {"data_id": ["3", "6"]},
+            "tasks": {"task_id": ["3", "6"]}}"#,
+    ).unwrap();
+
+    let study = Study::from_json(&study).unwrap();
+
+    assert_eq!(study.id(), 99);
+    assert_eq!(study.alias(), Some("OpenML-CC18"));
+    assert_eq!(study.task_ids(), &[3, 6]);
+    assert_eq!(study.dataset_ids(), &[3, 6]);
+
+    let study: serde_json::Value = serde_json::from_str(r#"{"alias": "no id"}"#).unwrap();
+    assert!(Study::from_json(&study).is_err());
+}
+
+#[test]
+fn dataset_description_from_json() {
+    let description: serde_json::Value = serde_json::from_str(
+        r#"{"id": "61", "name": "iris", "version": "1", "description": "Iris Plants Database",
+            "format": "ARFF", "creator": "R.A. Fisher", "collection_date": "1936",
+            "upload_date": "2014-04-06T23:23:39", "licence": "Public",
+            "url": "https://www.openml.org/data/v1/download/61/iris.arff",
+            "parquet_url": "https://data.openml.org/datasets/0000/0061/dataset_61.pq",
+            "default_target_attribute": "class", "status": "deactivated",
+            "ignore_attribute": ["a", "b"], "tag": ["study_1"]}"#,
+    ).unwrap();
+
+    let description = DatasetDescription::from_json(&description).unwrap();
+
+    assert_eq!(description.id, 61);
+    assert_eq!(description.name, "iris");
+    assert_eq!(description.creators, vec!["R.A. Fisher"]);
+    assert_eq!(description.licence, Some("Public".to_owned()));
+    assert_eq!(description.status, DatasetStatus::Deprecated);
+    assert_eq!(description.default_target_attribute, Some("class".to_owned()));
+    assert_eq!(description.row_id_attribute, None);
+    assert_eq!(description.ignore_attributes, vec!["a", "b"]);
+    assert!(description.parquet_url.unwrap().ends_with(".pq"));
+}
+
+#[test]
+fn malformed_dataset_description() {
+    let description: serde_json::Value =
+        serde_json::from_str(r#"{"id": "61", "name": "iris"}"#).unwrap();
+    assert!(DatasetDescription::from_json(&description).is_err());
+
+    let description: serde_json::Value =
+        serde_json::from_str(r#"{"name": "iris", "url": "https://example.com/iris.arff"}"#)
+            .unwrap();
+    assert!(DatasetDescription::from_json(&description).is_err());
+}
+
+#[test]
+fn feature_info_from_json() {
+    let feature: serde_json::Value = serde_json::from_str(
+        r#"{"index": "4", "name": "class", "data_type": "nominal",
+            "nominal_value": ["Iris-setosa", "Iris-versicolor", "Iris-virginica"],
+            "is_target": "true", "is_ignore": "false", "is_row_identifier": "false",
+            "number_of_missing_values": "0"}"#,
+    ).unwrap();
+
+    let feature = FeatureInfo::from_json(&feature).unwrap();
+
+    assert_eq!(feature.index, 4);
+    assert_eq!(feature.name, "class");
+    assert_eq!(feature.data_type, FeatureType::Nominal);
+    assert_eq!(feature.nominal_values.len(), 3);
+    assert!(feature.is_target);
+    assert!(!feature.is_ignore);
+    assert_eq!(feature.number_of_missing_values, 0);
+
+    let feature: serde_json::Value = serde_json::from_str(r#"{"name": "class"}"#).unwrap();
+    assert!(FeatureInfo::from_json(&feature).is_err());
+}
@@ -1,16 +1,88 @@
 //! implementations to load tasks from the OpenML API.
 use serde_json;
 
-use error::Result;
+use dataset::DataSet;
+use error::{Error, Result};
+use qualities::Qualities;
+use study::Study;
 use tasks::{SupervisedClassification, SupervisedRegression};
 
-use super::Id;
-use super::api_types::GenericResponse;
+use super::{config, FromOpenml, Id};
+use super::api_types::{json_string, parse_qualities, GenericResponse};
+use super::listing::{list_datasets, DatasetFilter};
 use super::web_access::get_cached;
 
+impl DataSet {
+    /// load data set by ID
+    pub fn from_openml<T: Id>(id: T) -> Result<Self> {
+        DataSet::load(id, None)
+    }
+
+    /// load data set by name. If no version is given, the oldest active version is loaded.
+    pub fn by_name(name: &str, version: Option<u32>) -> Result<Self> {
+        let filter = match version {
+            Some(v) => DatasetFilter::new().name(name).version(v).any_status(),
+            None => DatasetFilter::new().name(name),
+        };
+
+        match list_datasets(&filter)?.iter().min_by_key(|d| d.version) {
+            Some(summary) => DataSet::from_openml(summary.id),
+            None => Err(Error::NotFound(match version {
+                Some(v) => format!("data set {} (version {})", name, v),
+                None => format!("active data set {}", name),
+            })),
+        }
+    }
+
+    /// get the data set's qualities as computed by OpenML. Responses are cached. Qualities of data
+    /// sets that are not on OpenML can be computed with `qualities::compute`.
+    pub fn qualities(&self) -> Result<Qualities> {
+        match self.id() {
+            Some(id) => Qualities::from_openml(id),
+            None => Err(Error::NotFound(
+                "qualities of a data set that was not loaded from OpenML".to_owned(),
+            )),
+        }
+    }
+}
+
+impl FromOpenml for DataSet {
+    fn from_openml<T: Id>(id: T) -> Result<Self> {
+        DataSet::from_openml(id)
+    }
+}
+
+impl Qualities {
+    /// load qualities of the data set with the given ID
+    pub fn from_openml<T: Id>(id: T) -> Result<Self> {
+        let url = format!("{}/data/qualities/{}", config().json_api(), id.as_string());
+        let raw_qualities = get_cached(&url)?;
+        let response: GenericResponse = serde_json::from_str(&raw_qualities)?;
+
+        if let Some(err) = response.look_up("/error") {
+            return Err(Error::ApiError(format!(
+                "could not load qualities of data set {}: {}",
+                id.as_string(),
+                json_string(&err["message"]).unwrap_or_default()
+            )));
+        }
+
+        Ok(response
+            .look_up("/data_qualities/quality")
+            .map(parse_qualities)
+            .unwrap_or_default())
+    }
+}
+
+impl FromOpenml for Qualities {
+    fn from_openml<T: Id>(id: T) -> Result<Self> {
+        Qualities::from_openml(id)
+    }
+}
+
 impl SupervisedClassification {
     pub fn from_openml<'a, T: Id>(id: T) -> Result<Self> {
-        let url = format!("https://www.openml.org/api/v1/json/task/{}", id.as_string());
+        let url = format!("{}/task/{}", config().json_api(), id.as_string());
         let raw_task = get_cached(&url)?;
         let response: GenericResponse = serde_json::from_str(&raw_task)?;
 
@@ -26,7 +98,7 @@ impl SupervisedClassification {
 
 impl SupervisedRegression {
     pub fn from_openml<'a, T: Id>(id: T) -> Result<Self> {
-        let url = format!("https://www.openml.org/api/v1/json/task/{}", id.as_string());
+        let url = format!("{}/task/{}", config().json_api(), id.as_string());
         let raw_task = get_cached(&url)?;
         let response: GenericResponse = serde_json::from_str(&raw_task)?;
 
@@ -39,3 +111,43 @@ impl SupervisedRegression {
         }
     }
 }
+
+impl FromOpenml for SupervisedClassification {
+    fn from_openml<T: Id>(id: T) -> Result<Self> {
+        SupervisedClassification::from_openml(id)
+    }
+}
+
+impl FromOpenml for SupervisedRegression {
+    fn from_openml<T: Id>(id: T) -> Result<Self> {
+        SupervisedRegression::from_openml(id)
+    }
+}
+
+impl Study {
+    /// load study by ID or alias (e.g. `"OpenML-CC18"`)
+    pub fn from_openml<T: Id>(id: T) -> Result<Self> {
+        let url = format!("{}/study/{}", config().json_api(), id.as_string());
+        let raw_study = get_cached(&url)?;
+        let response: GenericResponse = serde_json::from_str(&raw_study)?;
+
+        if let Some(err) = response.look_up("/error") {
+            return Err(Error::ApiError(format!(
+                "could not load study {}: {}",
+                id.as_string(),
+                json_string(&err["message"]).unwrap_or_default()
+            )));
+        }
+
+        match response.look_up("/study") {
+            Some(study) => Study::from_json(study),
+            None => Err(Error::NotFound(format!("study {}", id.as_string()))),
+        }
+    }
+}
+
+impl FromOpenml for Study {
+    fn from_openml<T: Id>(id: T) -> Result<Self> {
+        Study::from_openml(id)
+    }
+}
@@ -0,0 +1,118 @@
+//! implementations to publish results on the OpenML server.
+use error::{Error, Result};
+use run::Run;
+use xml::find_element;
+
+use super::{config, Config};
+use super::web_access::{post_multipart, FormFile};
+
+impl Run {
+    /// upload run to the server configured in `~/.openml/config` and return the new run's ID
+    pub fn upload(&self) -> Result<u32> {
+        self.upload_to(config())
+    }
+
+    /// upload run to the server specified in `config` and return the new run's ID
+    pub fn upload_to(&self, config: &Config) -> Result<u32> {
+        if config.apikey.is_none() {
+            return Err(Error::MissingApiKey);
+        }
+
+        let url = config.authenticate(&format!("{}/run", config.xml_api()));
+
+        let description = self.description_xml();
+        let files = [
+            FormFile {
+                name: "description",
+                filename: "description.xml",
+                content_type: "text/xml",
+                content: &description,
+            },
+            FormFile {
+                name: "predictions",
+                filename: "predictions.arff",
+                content_type: "text/plain",
+                content: self.predictions(),
+            },
+        ];
+
+        let response = post_multipart(&url, &files)?;
+
+        find_element(&response, "oml:run_id")
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| Error::ApiError(format!("unexpected response: {}", response)))
+    }
+}
+
+#[test]
+fn upload_run() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // stand-in server that records the request and answers with a fixed run id
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut head = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.to_lowercase().starts_with("content-length:") {
+                content_length = line[15..].trim().parse().unwrap();
+            }
+            if line == "\r\n" {
+                break;
+            }
+            head += &line;
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+
+        let response = "<oml:upload_run xmlns:oml=\"http://openml.org/openml\">\n\
+                        <oml:run_id>1234</oml:run_id>\n\
+                        </oml:upload_run>\n";
+        write!(
+            &stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        ).unwrap();
+
+        (head, String::from_utf8(body).unwrap())
+    });
+
+    let config = Config {
+        apikey: Some("secret".to_owned()),
+        server: format!("http://{}/api/v1/xml", addr),
+        cachedir: None,
+    };
+
+    let run = Run::new(59, 7, "@RELATION predictions\n".to_owned());
+    let run_id = run.upload_to(&config).unwrap();
+
+    let (head, body) = server.join().unwrap();
+
+    assert_eq!(run_id, 1234);
+    assert!(head.starts_with("POST /api/v1/xml/run?api_key=secret HTTP/1.1\r\n"));
+    assert!(head.contains("multipart/form-data; boundary="));
+    assert!(body.contains("name=\"description\"; filename=\"description.xml\""));
+    assert!(body.contains("<oml:task_id>59</oml:task_id>"));
+    assert!(body.contains("name=\"predictions\"; filename=\"predictions.arff\""));
+    assert!(body.contains("@RELATION predictions"));
+}
+
+#[test]
+fn upload_requires_api_key() {
+    let run = Run::new(59, 7, String::new());
+    match run.upload_to(&Config::default()) {
+        Err(Error::MissingApiKey) => {}
+        r => panic!("expected MissingApiKey error, got {:?}", r),
+    }
+}
@@ -0,0 +1,516 @@
+//! Listing and filtering of OpenML entities
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json;
+
+use dataset::DatasetStatus;
+use error::{Error, Result};
+use qualities::Qualities;
+use tasks::{TaskStatus, TaskType};
+
+use super::api_types::{json_list, json_string, json_u32, parse_qualities, GenericResponse};
+use super::config;
+use super::web_access::get_cached_max_age;
+
+/// Error code returned by the API if a data set listing is empty
+const NO_DATASETS: &str = "372";
+
+/// Error code returned by the API if a task listing is empty
+const NO_TASKS: &str = "482";
+
+/// Cached listings are downloaded again after this time
+const LISTING_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Filter path segments, as in `/tag/study_14/limit/100`
+#[derive(Debug, Clone, Default)]
+struct Segments(Vec<(&'static str, String)>);
+
+impl Segments {
+    /// set a filter, replacing any previous value
+    fn set<T: ToString>(&mut self, key: &'static str, value: T) {
+        let value = value.to_string();
+        match self.0.iter_mut().find(|&&mut (k, _)| k == key) {
+            Some(item) => item.1 = value,
+            None => self.0.push((key, value)),
+        }
+    }
+
+    fn set_range(&mut self, key: &'static str, min: u64, max: u64) {
+        self.set(key, format!("{}..{}", min, max))
+    }
+
+    fn to_path(&self) -> String {
+        self.0
+            .iter()
+            .map(|(k, v)| format!("/{}/{}", k, percent_encode(v)))
+            .collect()
+    }
+}
+
+/// Percent-encode a filter value for use as path segment. Commas are kept, because they separate
+/// the items of list filters.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b',' => {
+                encoded.push(b as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    encoded
+}
+
+/// Filter for listing data sets
+///
+/// Without any filter, all active data sets are listed.
+/// ```
+/// # use openml::DatasetFilter;
+/// let filter = DatasetFilter::new()
+///     .tag("uci")
+///     .number_of_instances(100, 10000)
+///     .limit(50);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DatasetFilter {
+    segments: Segments,
+}
+
+impl DatasetFilter {
+    /// create an empty filter
+    pub fn new() -> Self {
+        DatasetFilter::default()
+    }
+
+    /// only list data sets with the given tag
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.segments.set("tag", tag);
+        self
+    }
+
+    /// only list data sets with the given status (default: active)
+    pub fn status(mut self, status: DatasetStatus) -> Self {
+        self.segments.set("status", status.as_str());
+        self
+    }
+
+    /// list data sets regardless of their status
+    pub fn any_status(mut self) -> Self {
+        self.segments.set("status", "all");
+        self
+    }
+
+    /// only list data sets with the given name
+    pub fn name(mut self, name: &str) -> Self {
+        self.segments.set("data_name", name);
+        self
+    }
+
+    /// only list data sets with the given version
+    pub fn version(mut self, version: u32) -> Self {
+        self.segments.set("data_version", version);
+        self
+    }
+
+    /// only list data sets with the given IDs
+    pub fn ids(mut self, ids: &[u32]) -> Self {
+        let ids: Vec<_> = ids.iter().map(|id| id.to_string()).collect();
+        self.segments.set("data_id", ids.join(","));
+        self
+    }
+
+    /// only list data sets with a number of instances in the range `min..=max`
+    pub fn number_of_instances(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_instances", min, max);
+        self
+    }
+
+    /// only list data sets with a number of features in the range `min..=max`
+    pub fn number_of_features(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_features", min, max);
+        self
+    }
+
+    /// only list data sets with a number of classes in the range `min..=max`
+    pub fn number_of_classes(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_classes", min, max);
+        self
+    }
+
+    /// only list data sets with a number of missing values in the range `min..=max`
+    pub fn number_of_missing_values(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_missing_values", min, max);
+        self
+    }
+
+    /// return at most `limit` results
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.segments.set("limit", limit);
+        self
+    }
+
+    /// skip the first `offset` results
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.segments.set("offset", offset);
+        self
+    }
+}
+
+/// Summary of a data set, as returned by `list_datasets`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetSummary {
+    pub id: u32,
+    pub name: String,
+    pub version: u32,
+    pub status: DatasetStatus,
+    pub format: String,
+    pub number_of_instances: Option<u64>,
+    pub number_of_features: Option<u64>,
+    pub number_of_classes: Option<u64>,
+    pub number_of_missing_values: Option<u64>,
+
+    /// all qualities included in the listing
+    pub qualities: Qualities,
+}
+
+impl DatasetSummary {
+    fn from_json(item: &serde_json::Value) -> Result<Self> {
+        let id = json_u32(&item["did"])
+            .ok_or_else(|| Error::ParseError("data set listing item without ID".to_owned()))?;
+        let qualities = parse_qualities(&item["quality"]);
+        let count = |name: &str| qualities.get(name).map(|v| v as u64);
+
+        Ok(DatasetSummary {
+            id,
+            name: json_string(&item["name"]).unwrap_or_default(),
+            version: json_u32(&item["version"]).unwrap_or(1),
+            status: json_string(&item["status"])
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DatasetStatus::Active),
+            format: json_string(&item["format"]).unwrap_or_default(),
+            number_of_instances: count("NumberOfInstances"),
+            number_of_features: count("NumberOfFeatures"),
+            number_of_classes: count("NumberOfClasses"),
+            number_of_missing_values: count("NumberOfMissingValues"),
+            qualities,
+        })
+    }
+}
+
+/// List data sets on OpenML that match the filter. Responses are cached for a day.
+pub fn list_datasets(filter: &DatasetFilter) -> Result<Vec<DatasetSummary>> {
+    let url = format!("{}/data/list{}", config().json_api(), filter.segments.to_path());
+    let response = get_listing(&url, NO_DATASETS)?;
+
+    let items = match response.as_ref().and_then(|r| r.look_up("/data/dataset")) {
+        Some(items) => json_list(items),
+        None => return Ok(vec![]),
+    };
+
+    Ok(items
+        .into_iter()
+        .filter_map(|item| skip_invalid(DatasetSummary::from_json(item)))
+        .collect())
+}
+
+/// Get the qualities of many data sets at once. The qualities of each data set are requested
+/// separately, so this is most useful in combination with the response cache.
+pub fn list_qualities(ids: &[u32]) -> Result<HashMap<u32, Qualities>> {
+    ids.iter()
+        .map(|&id| Ok((id, Qualities::from_openml(id)?)))
+        .collect()
+}
+
+/// Filter for listing tasks
+///
+/// Without any filter, all active tasks are listed. Select all classification tasks with less
+/// than 10000 instances:
+/// ```
+/// # use openml::{TaskFilter, TaskType};
+/// let filter = TaskFilter::new()
+///     .task_type(TaskType::SupervisedClassification)
+///     .number_of_instances(0, 9999);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    segments: Segments,
+}
+
+impl TaskFilter {
+    /// create an empty filter
+    pub fn new() -> Self {
+        TaskFilter::default()
+    }
+
+    /// only list tasks of the given type
+    pub fn task_type(mut self, task_type: TaskType) -> Self {
+        self.segments.set("type", task_type.id());
+        self
+    }
+
+    /// only list tasks with the given tag
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.segments.set("tag", tag);
+        self
+    }
+
+    /// only list tasks on the data set with the given ID
+    pub fn dataset(mut self, dataset_id: u32) -> Self {
+        self.segments.set("data_id", dataset_id);
+        self
+    }
+
+    /// only list tasks on data sets with the given name
+    pub fn dataset_name(mut self, name: &str) -> Self {
+        self.segments.set("data_name", name);
+        self
+    }
+
+    /// only list tasks with the given status (default: active)
+    pub fn status(mut self, status: TaskStatus) -> Self {
+        self.segments.set("status", status.as_str());
+        self
+    }
+
+    /// only list tasks on data sets with a number of instances in the range `min..=max`
+    pub fn number_of_instances(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_instances", min, max);
+        self
+    }
+
+    /// only list tasks on data sets with a number of features in the range `min..=max`
+    pub fn number_of_features(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_features", min, max);
+        self
+    }
+
+    /// only list tasks on data sets with a number of classes in the range `min..=max`
+    pub fn number_of_classes(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_classes", min, max);
+        self
+    }
+
+    /// only list tasks on data sets with a number of missing values in the range `min..=max`
+    pub fn number_of_missing_values(mut self, min: u64, max: u64) -> Self {
+        self.segments.set_range("number_missing_values", min, max);
+        self
+    }
+
+    /// return at most `limit` results
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.segments.set("limit", limit);
+        self
+    }
+
+    /// skip the first `offset` results
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.segments.set("offset", offset);
+        self
+    }
+}
+
+/// Summary of a task, as returned by `list_tasks`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskSummary {
+    pub id: u32,
+
+    /// task type, or `None` if the type is not known to this crate
+    pub task_type: Option<TaskType>,
+
+    pub dataset_id: u32,
+    pub dataset_name: String,
+    pub status: TaskStatus,
+    pub estimation_procedure: Option<u32>,
+    pub target_feature: Option<String>,
+    pub number_of_instances: Option<u64>,
+    pub number_of_features: Option<u64>,
+    pub number_of_classes: Option<u64>,
+    pub number_of_missing_values: Option<u64>,
+
+    /// all data set qualities included in the listing
+    pub qualities: Qualities,
+
+    pub tags: Vec<String>,
+}
+
+impl TaskSummary {
+    fn from_json(item: &serde_json::Value) -> Result<Self> {
+        let id = json_u32(&item["task_id"])
+            .ok_or_else(|| Error::ParseError("task listing item without ID".to_owned()))?;
+        let dataset_id = json_u32(&item["did"])
+            .ok_or_else(|| Error::ParseError(format!("task {} without data set ID", id)))?;
+        let qualities = parse_qualities(&item["quality"]);
+        let count = |name: &str| qualities.get(name).map(|v| v as u64);
+
+        let mut estimation_procedure = None;
+        let mut target_feature = None;
+        for input in json_list(&item["input"]) {
+            match json_string(&input["name"]).as_deref() {
+                Some("estimation_procedure") => estimation_procedure = json_u32(&input["value"]),
+                Some("target_feature") => target_feature = json_string(&input["value"]),
+                _ => {}
+            }
+        }
+
+        Ok(TaskSummary {
+            id,
+            task_type: json_u32(&item["task_type_id"]).and_then(TaskType::from_id),
+            dataset_id,
+            dataset_name: json_string(&item["name"]).unwrap_or_default(),
+            status: json_string(&item["status"])
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(TaskStatus::Active),
+            estimation_procedure,
+            target_feature,
+            number_of_instances: count("NumberOfInstances"),
+            number_of_features: count("NumberOfFeatures"),
+            number_of_classes: count("NumberOfClasses"),
+            number_of_missing_values: count("NumberOfMissingValues"),
+            qualities,
+            tags: json_list(&item["tag"]).into_iter().filter_map(json_string).collect(),
+        })
+    }
+}
+
+/// List tasks on OpenML that match the filter. Responses are cached for a day.
+pub fn list_tasks(filter: &TaskFilter) -> Result<Vec<TaskSummary>> {
+    let url = format!("{}/task/list{}", config().json_api(), filter.segments.to_path());
+    let response = get_listing(&url, NO_TASKS)?;
+
+    let items = match response.as_ref().and_then(|r| r.look_up("/tasks/task")) {
+        Some(items) => json_list(items),
+        None => return Ok(vec![]),
+    };
+
+    Ok(items
+        .into_iter()
+        .filter_map(|item| skip_invalid(TaskSummary::from_json(item)))
+        .collect())
+}
+
+/// Log and skip listing items that can not be parsed, so that a single malformed item does not
+/// fail the whole listing.
+fn skip_invalid<T>(item: Result<T>) -> Option<T> {
+    item.map_err(|e| warn!("Skipping invalid listing item: {:?}", e)).ok()
+}
+
+/// Query a listing URL. Returns `None` if the server reports the `no_results` error code.
+fn get_listing(url: &str, no_results: &str) -> Result<Option<GenericResponse>> {
+    parse_listing(&get_cached_max_age(url, LISTING_MAX_AGE)?, no_results)
+}
+
+fn parse_listing(raw: &str, no_results: &str) -> Result<Option<GenericResponse>> {
+    let response: GenericResponse = serde_json::from_str(raw)?;
+
+    if let Some(err) = response.look_up("/error") {
+        return match json_string(&err["code"]) {
+            Some(ref code) if code == no_results => Ok(None),
+            code => Err(Error::ApiError(format!(
+                "error {}: {}",
+                code.unwrap_or_default(),
+                json_string(&err["message"]).unwrap_or_default()
+            ))),
+        };
+    }
+
+    Ok(Some(response))
+}
+
+#[test]
+fn dataset_filter_path() {
+    let filter = DatasetFilter::new()
+        .tag("uci")
+        .number_of_instances(100, 1000)
+        .limit(10)
+        .tag("study_14");
+
+    assert_eq!(
+        filter.segments.to_path(),
+        "/tag/study_14/number_instances/100..1000/limit/10"
+    );
+
+    let filter = DatasetFilter::new().name("a b/c").ids(&[1, 2]);
+    assert_eq!(filter.segments.to_path(), "/data_name/a%20b%2Fc/data_id/1,2");
+}
+
+#[test]
+fn empty_listings() {
+    let datasets = r#"{"error": {"code": "372", "message": "No results"}}"#;
+    let tasks = r#"{"error": {"code": "482", "message": "No results"}}"#;
+
+    assert!(parse_listing(datasets, NO_DATASETS).unwrap().is_none());
+    assert!(parse_listing(tasks, NO_TASKS).unwrap().is_none());
+    assert!(parse_listing(tasks, NO_DATASETS).is_err());
+}
+
+#[test]
+fn parse_dataset_summary() {
+    let item: serde_json::Value = serde_json::from_str(
+        r#"{"did": 61, "name": "iris", "version": "1", "status": "active", "format": "ARFF",
+            "quality": [{"name": "NumberOfClasses", "value": "3.0"},
+                        {"name": "NumberOfInstances", "value": "150.0"},
+                        {"name": "MajorityClassSize", "value": "50.0"}]}"#,
+    ).unwrap();
+
+    let summary = DatasetSummary::from_json(&item).unwrap();
+
+    assert_eq!(summary.id, 61);
+    assert_eq!(summary.name, "iris");
+    assert_eq!(summary.version, 1);
+    assert_eq!(summary.status, DatasetStatus::Active);
+    assert_eq!(summary.number_of_instances, Some(150));
+    assert_eq!(summary.number_of_classes, Some(3));
+    assert_eq!(summary.number_of_features, None);
+    assert_eq!(summary.qualities.get("MajorityClassSize"), Some(50.0));
+
+    let item: serde_json::Value = serde_json::from_str(r#"{"name": "no id"}"#).unwrap();
+    assert!(DatasetSummary::from_json(&item).is_err());
+}
+
+#[test]
+fn parse_task_summary() {
+    let item: serde_json::Value = serde_json::from_str(
+        r#"{"task_id": 59, "task_type_id": 1, "task_type": "Supervised Classification",
+            "did": 61, "name": "iris", "status": "active", "format": "ARFF",
+            "input": [{"name": "estimation_procedure", "value": "1"},
+                      {"name": "source_data", "value": "61"},
+                      {"name": "target_feature", "value": "class"}],
+            "quality": [{"name": "NumberOfInstances", "value": "150.0"}],
+            "tag": ["basic", "study_1"]}"#,
+    ).unwrap();
+
+    let summary = TaskSummary::from_json(&item).unwrap();
+
+    assert_eq!(summary.id, 59);
+    assert_eq!(summary.task_type, Some(TaskType::SupervisedClassification));
+    assert_eq!(summary.dataset_id, 61);
+    assert_eq!(summary.estimation_procedure, Some(1));
+    assert_eq!(summary.target_feature, Some("class".to_owned()));
+    assert_eq!(summary.number_of_instances, Some(150));
+    assert_eq!(summary.tags, vec!["basic", "study_1"]);
+
+    let item: serde_json::Value = serde_json::from_str(r#"{"task_id": 1}"#).unwrap();
+    assert!(TaskSummary::from_json(&item).is_err());
+}
+
+#[test]
+fn parse_quality_values() {
+    let json: serde_json::Value = serde_json::from_str(
+        r#"[{"name": "ClassEntropy", "value": "1.584962500721156"},
+            {"name": "NumberOfClasses", "value": "3.0"},
+            {"name": "NumberOfInstances", "value": 150},
+            {"name": "MaxNominalAttDistinctValues", "value": []}]"#,
+    ).unwrap();
+
+    let qualities = parse_qualities(&json);
+
+    assert_eq!(qualities.len(), 3);
+    assert_eq!(qualities.number_of_instances(), Some(150));
+    assert_eq!(qualities.number_of_classes(), Some(3));
+    assert_eq!(qualities.number_of_features(), None);
+    assert!((qualities.class_entropy().unwrap() - 1.585).abs() < 1e-3);
+    assert_eq!(qualities.get("MaxNominalAttDistinctValues"), None);
+}
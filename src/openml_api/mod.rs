@@ -1,13 +1,33 @@
 //! Cached access to the OpenML REST API
 
 mod api_types;
-mod file_lock;
+mod config;
+pub(crate) mod file_lock;
 mod impls_from_json;
 mod impls_from_openml;
+mod impls_upload;
+mod listing;
 mod web_access;
 
 use std::borrow::Cow;
 
+use app_dirs::AppInfo;
+
+use error::Result;
+
+pub use self::config::Config;
+pub use self::listing::{list_datasets, list_qualities, list_tasks, DatasetFilter, DatasetSummary,
+                        TaskFilter, TaskSummary};
+pub(crate) use self::config::config;
+
+/// Application info used to locate cache and data directories
+pub(crate) const APP_INFO: AppInfo = AppInfo{name: "openml-rust", author: "openml-rust"};
+
+/// Types that can be loaded from OpenML
+pub trait FromOpenml: Sized {
+    fn from_openml<T: Id>(id: T) -> Result<Self>;
+}
+
 pub trait Id {
     fn as_string(&self) -> Cow<str>;
     fn as_u32(&self) -> u32;
@@ -1,35 +1,43 @@
 //! Access the OpenML REST API
 
-use std::fs::{File, OpenOptions};
+use std::fs::{self, File, OpenOptions};
 use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::time::Duration;
 
-use app_dirs::{app_root, AppDataType, AppInfo};
+use app_dirs::{app_root, AppDataType};
 use futures::{Future, Stream};
-use hyper::Client;
+use hyper::{Client, Method, Request, StatusCode};
+use hyper::header::ContentLength;
 use hyper_tls::HttpsConnector;
 use tokio_core::reactor::Core;
 
-use error::Result;
+use error::{Error, Result};
+use xml::find_element;
 
+use super::{config, APP_INFO};
 use super::file_lock::{ExclusiveLock, SharedLock};
 
-const APP_INFO: AppInfo = AppInfo{name: "openml-rust", author: "openml-rust"};
-
 /// Query a URL. If possible read the response from local cache
 pub fn get_cached(url: &str) -> Result<String> {
+    Ok(String::from_utf8(get_cached_bytes(url)?)?)
+}
+
+/// Query a URL for binary data, such as parquet files. If possible read the response from local
+/// cache
+pub fn get_cached_bytes(url: &str) -> Result<Vec<u8>> {
     // todo: is there a potential race condition with a process locking the file for reading while
     //       the writer has created but not yet locked the file?
 
-    let mut path = app_root(AppDataType::UserCache, &APP_INFO)?;
-    path.push(url_to_file(url));
+    let path = cache_path(url)?;
 
     loop {
         match File::open(&path) {
             Ok(f) => {
                 info!("Loading cached {}", url);
                 let mut file = SharedLock::new(f)?;
-                let mut data = String::new();
-                file.read_to_string(&mut data)?;
+                let mut data = Vec::new();
+                file.read_to_end(&mut data)?;
                 return Ok(data);
             }
             Err(_) => {}
@@ -48,15 +56,50 @@ pub fn get_cached(url: &str) -> Result<String> {
                 info!("Downloading {}", url);
                 let mut file = ExclusiveLock::new(f)?;
                 let data = download(url)?;
-                file.write_all(data.as_bytes())?;
+                file.write_all(&data)?;
                 return Ok(data);
             }
         }
     }
 }
 
-/// Query a URL.
-fn download(url: &str) -> Result<String> {
+/// Query a URL, like `get_cached`, but download the response again if the cached copy is older
+/// than `max_age`
+pub fn get_cached_max_age(url: &str, max_age: Duration) -> Result<String> {
+    let path = cache_path(url)?;
+    let expired = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > max_age);
+
+    if expired {
+        info!("Cached {} expired", url);
+        if let Err(e) = fs::remove_file(&path) {
+            warn!("Error while removing expired cache file: {:?}", e);
+        }
+    }
+
+    get_cached(url)
+}
+
+/// Path of the file in which the response of a URL is cached
+fn cache_path(url: &str) -> Result<PathBuf> {
+    let mut path = match config().cachedir {
+        Some(ref dir) => {
+            fs::create_dir_all(dir)?;
+            dir.clone()
+        }
+        None => app_root(AppDataType::UserCache, &APP_INFO)?,
+    };
+    path.push(url_to_file(url));
+    Ok(path)
+}
+
+/// Query a URL. The API key is attached if the URL points to the configured server.
+fn download(url: &str) -> Result<Vec<u8>> {
+    let url = config().authenticate(url);
+
     let mut core = Core::new()?;
     let handle = core.handle();
     let client = Client::configure()
@@ -75,7 +118,73 @@ fn download(url: &str) -> Result<String> {
         });
         core.run(work)?
     }
+    Ok(bytes)
+}
+
+/// A file attached to a multipart form
+pub struct FormFile<'a> {
+    pub name: &'a str,
+    pub filename: &'a str,
+    pub content_type: &'a str,
+    pub content: &'a str,
+}
+
+/// Send files as `multipart/form-data` POST request to a URL and return the response body.
+///
+/// Responses are never cached. Non-success status codes are reported as `Error::ApiError`.
+pub fn post_multipart(url: &str, files: &[FormFile]) -> Result<String> {
+    const BOUNDARY: &str = "----openml-rust-form-boundary";
+
+    let mut body = String::new();
+    for file in files {
+        body += &format!("--{}\r\n", BOUNDARY);
+        body += &format!(
+            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+            file.name, file.filename
+        );
+        body += &format!("Content-Type: {}\r\n\r\n", file.content_type);
+        body += file.content;
+        body += "\r\n";
+    }
+    body += &format!("--{}--\r\n", BOUNDARY);
+
+    let mut core = Core::new()?;
+    let handle = core.handle();
+    let client = Client::configure()
+        .connector(HttpsConnector::new(4, &handle)?)
+        .build(&handle);
+
+    let mut req = Request::new(Method::Post, url.parse()?);
+    req.headers_mut().set(ContentLength(body.len() as u64));
+    req.headers_mut().set_raw(
+        "Content-Type",
+        format!("multipart/form-data; boundary={}", BOUNDARY),
+    );
+    req.set_body(body);
+
+    let mut status = StatusCode::Ok;
+    let mut bytes = Vec::new();
+    {
+        let work = client.request(req).and_then(|res| {
+            status = res.status();
+            res.body().for_each(|chunk| {
+                bytes.extend_from_slice(&chunk);
+                Ok(())
+            })
+        });
+        core.run(work)?
+    }
     let result = String::from_utf8(bytes)?;
+
+    if !status.is_success() {
+        let code = find_element(&result, "oml:code");
+        let message = match (code, find_element(&result, "oml:message")) {
+            (Some(code), Some(msg)) => format!("{} (error {}: {})", status, code, msg),
+            _ => format!("{}", status),
+        };
+        return Err(Error::ApiError(message));
+    }
+
     Ok(result)
 }
 
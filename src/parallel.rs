@@ -0,0 +1,131 @@
+//! Runners that evaluate the folds of a task concurrently
+
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+
+use measure_accumulator::MergeableAccumulator;
+use procedures::Procedure;
+use tasks::{SupervisedClassification, SupervisedRegression};
+use tasks::runner::load_xy;
+
+impl SupervisedClassification {
+    /// like `run`, but the folds are evaluated concurrently on rayon's thread pool. Each fold is
+    /// scored by its own measure, and the measures are merged in fold order, so the result does
+    /// not depend on the order in which folds finish.
+    pub fn run_parallel<X, Y, F, M>(&self, flow: F) -> M
+    where
+        F: Fn(&mut Iterator<Item = (&[X], &Y)>, &mut Iterator<Item = &[X]>)
+            -> Box<Iterator<Item = Y>>
+            + Sync,
+        X: DeserializeOwned + Sync,
+        Y: DeserializeOwned + Send + Sync,
+        M: MergeableAccumulator<Y> + Send,
+    {
+        let (x, y, n_cols) = load_xy(&self.source_data, "Supervised Classification");
+
+        run_folds_parallel(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            flow,
+        )
+    }
+}
+
+impl SupervisedRegression {
+    /// like `run`, but the folds are evaluated concurrently on rayon's thread pool. Each fold is
+    /// scored by its own measure, and the measures are merged in fold order, so the result does
+    /// not depend on the order in which folds finish.
+    pub fn run_parallel<X, Y, F, M>(&self, flow: F) -> M
+    where
+        F: Fn(&mut Iterator<Item = (&[X], &Y)>, &mut Iterator<Item = &[X]>)
+            -> Box<Iterator<Item = Y>>
+            + Sync,
+        X: DeserializeOwned + Sync,
+        Y: DeserializeOwned + Send + Sync,
+        M: MergeableAccumulator<Y> + Send,
+    {
+        let (x, y, n_cols) = load_xy(&self.source_data, "Supervised Regression");
+
+        run_folds_parallel(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            flow,
+        )
+    }
+}
+
+/// Parallel counterpart of `run_folds`. The flow is called concurrently for all folds, and the
+/// measure of each fold is merged into the result in the same order as `run_folds` would pass
+/// the folds to the flow.
+fn run_folds_parallel<'a, R, Y, G, F, M>(
+    procedure: &Procedure,
+    rows: G,
+    y: &'a [Y],
+    flow: F,
+) -> M
+where
+    R: 'a + ?Sized + Sync,
+    Y: Sync,
+    G: Fn(usize) -> &'a R + Sync,
+    F: Fn(&mut Iterator<Item = (&R, &Y)>, &mut Iterator<Item = &R>) -> Box<Iterator<Item = Y>>
+        + Sync,
+    M: MergeableAccumulator<Y> + Send,
+{
+    let folds: Vec<_> = procedure.iter().collect();
+
+    let measures: Vec<M> = folds
+        .par_iter()
+        .map(|fold| {
+            let mut train = fold.trainset.iter().map(|&i| (rows(i), &y[i]));
+            let mut test = fold.testset.iter().map(|&i| rows(i));
+
+            let mut measure = M::new();
+            for (&i, pred) in fold.testset.iter().zip(flow(&mut train, &mut test)) {
+                measure.update_one(&y[i], &pred);
+            }
+            measure
+        })
+        .collect();
+
+    let mut result = M::new();
+    for measure in measures {
+        result.merge(measure);
+    }
+    result
+}
+
+#[test]
+fn parallel_folds() {
+    use measure_accumulator::{MeasureAccumulator, PredictiveAccuracy};
+    use procedures::{Fold, FrozenSets};
+
+    let folds: Vec<_> = (0..8)
+        .map(|f| {
+            let mut fold = Fold::new(0, f);
+            fold.trainset = (0..8).filter(|&i| i != f).collect();
+            fold.testset = vec![f];
+            fold
+        })
+        .collect();
+    let procedure = FrozenSets {
+        folds: vec![folds],
+    };
+
+    let x: Vec<_> = (0..8).map(|i| i * 10).collect();
+    let y: Vec<_> = (0..8).collect();
+
+    let accuracy: PredictiveAccuracy<_> = run_folds_parallel(
+        &procedure,
+        |i| &x[i],
+        &y,
+        |train, test| {
+            assert_eq!(train.count(), 7);
+            let pred: Vec<_> = test.map(|&x| if x < 60 { x / 10 } else { 0 }).collect();
+            Box::new(pred.into_iter())
+        },
+    );
+
+    assert_eq!(accuracy.result(), 0.75);
+}
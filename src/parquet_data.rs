@@ -0,0 +1,190 @@
+//! Support for data sets in parquet format
+//!
+//! Parquet files are converted to the same ARFF data set that is loaded from ARFF files, so the
+//! rest of the crate does not depend on the file format. The `arff` crate does not export the
+//! types needed to build columns directly, so the data is converted to ARFF text and parsed.
+
+use arff::dynamic::DataSet as ArffDataSet;
+use bytes::Bytes;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+
+use dataset::{quote, FeatureInfo, FeatureType};
+use error::{Error, Result};
+
+/// Parse a parquet file. Parquet files do not declare the levels of nominal columns, so column
+/// types and levels are taken from `features`.
+pub(crate) fn parse(
+    data: Vec<u8>,
+    relation: &str,
+    features: &[FeatureInfo],
+) -> Result<ArffDataSet> {
+    if features.is_empty() {
+        return Err(parse_error("feature information is required to read parquet files"));
+    }
+
+    let mut features: Vec<_> = features.iter().collect();
+    features.sort_by_key(|f| f.index);
+
+    let mut arff = format!("@RELATION {}\n", quote(relation));
+    for f in &features {
+        let kind = match f.data_type {
+            FeatureType::Numeric => "NUMERIC".to_owned(),
+            FeatureType::Nominal => {
+                let levels: Vec<_> = f.nominal_values.iter().map(|l| quote(l)).collect();
+                format!("{{{}}}", levels.join(","))
+            }
+            FeatureType::String | FeatureType::Date => "STRING".to_owned(),
+        };
+        arff.push_str(&format!("@ATTRIBUTE {} {}\n", quote(&f.name), kind));
+    }
+    arff.push_str("@DATA\n");
+
+    let reader = SerializedFileReader::new(Bytes::from(data)).map_err(parse_error)?;
+
+    let mut positions: Option<Vec<usize>> = None;
+    for row in reader.get_row_iter(None).map_err(parse_error)? {
+        let row = row.map_err(parse_error)?;
+        let fields: Vec<_> = row.get_column_iter().collect();
+
+        if positions.is_none() {
+            positions = Some(features
+                .iter()
+                .map(|f| {
+                    fields
+                        .iter()
+                        .position(|&(name, _)| *name == f.name)
+                        .ok_or_else(|| parse_error(format!("column {} is missing", f.name)))
+                })
+                .collect::<Result<_>>()?);
+        }
+
+        let values: Vec<_> = positions
+            .as_ref()
+            .unwrap()
+            .iter()
+            .zip(&features)
+            .map(|(&p, f)| arff_value(fields[p].1, f.data_type))
+            .collect();
+
+        arff.push_str(&values.join(","));
+        arff.push('\n');
+    }
+
+    Ok(ArffDataSet::from_str(&arff)?)
+}
+
+fn parse_error<E: ToString>(e: E) -> Error {
+    Error::ParseError(e.to_string())
+}
+
+/// format a parquet field as ARFF value
+fn arff_value(field: &Field, data_type: FeatureType) -> String {
+    let value = match *field {
+        Field::Null => return "?".to_owned(),
+        Field::Float(x) if x.is_nan() => return "?".to_owned(),
+        Field::Double(x) if x.is_nan() => return "?".to_owned(),
+        Field::Bool(x) => x.to_string(),
+        Field::Byte(x) => x.to_string(),
+        Field::Short(x) => x.to_string(),
+        Field::Int(x) => x.to_string(),
+        Field::Long(x) => x.to_string(),
+        Field::UByte(x) => x.to_string(),
+        Field::UShort(x) => x.to_string(),
+        Field::UInt(x) => x.to_string(),
+        Field::ULong(x) => x.to_string(),
+        Field::Float(x) => x.to_string(),
+        Field::Double(x) => x.to_string(),
+        Field::Str(ref s) => s.clone(),
+        ref other => other.to_string(),
+    };
+
+    match data_type {
+        FeatureType::Numeric => value,
+        FeatureType::Nominal | FeatureType::String | FeatureType::Date => quote(&value),
+    }
+}
+
+#[test]
+fn format_fields() {
+    assert_eq!(arff_value(&Field::Null, FeatureType::Numeric), "?");
+    assert_eq!(arff_value(&Field::Double(f64::NAN), FeatureType::Numeric), "?");
+    assert_eq!(arff_value(&Field::Double(1.5), FeatureType::Numeric), "1.5");
+    assert_eq!(arff_value(&Field::Long(3), FeatureType::Nominal), "3");
+    assert_eq!(
+        arff_value(&Field::Str("Iris setosa".to_owned()), FeatureType::Nominal),
+        "'Iris setosa'"
+    );
+    assert_eq!(
+        arff_value(&Field::Str("it's".to_owned()), FeatureType::Nominal),
+        "\"it's\""
+    );
+}
+
+#[test]
+fn parquet_matches_arff() {
+    use std::sync::Arc;
+
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    use dataset::test_feature;
+
+    let schema = parse_message_type(
+        "message data { OPTIONAL DOUBLE x; OPTIONAL BYTE_ARRAY class (UTF8); }",
+    ).unwrap();
+    let properties = WriterProperties::builder().build();
+
+    let mut data = Vec::new();
+    {
+        let mut writer =
+            SerializedFileWriter::new(&mut data, Arc::new(schema), Arc::new(properties)).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+
+        let mut col = row_group.next_column().unwrap().unwrap();
+        col.typed::<DoubleType>()
+            .write_batch(&[1.5, 2.0], Some(&[1, 0, 1]), None)
+            .unwrap();
+        col.close().unwrap();
+
+        let classes: Vec<ByteArray> = vec!["it's".into(), "neg".into()];
+        let mut col = row_group.next_column().unwrap().unwrap();
+        col.typed::<ByteArrayType>()
+            .write_batch(&classes, Some(&[1, 0, 1]), None)
+            .unwrap();
+        col.close().unwrap();
+
+        row_group.close().unwrap();
+        writer.close().unwrap();
+    }
+
+    let features = vec![
+        FeatureInfo {
+            index: 1,
+            ..test_feature("class", FeatureType::Nominal, &["neg", "it's"])
+        },
+        test_feature("x", FeatureType::Numeric, &[]),
+    ];
+
+    let from_parquet = parse(data, "test", &features).unwrap();
+    let from_arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE class {neg, \"it's\"}\n\
+         @DATA\n\
+         1.5, \"it's\"\n\
+         ?, ?\n\
+         2, neg\n",
+    ).unwrap();
+
+    assert_eq!(
+        from_parquet.col_names().collect::<Vec<_>>(),
+        from_arff.col_names().collect::<Vec<_>>()
+    );
+    assert_eq!(from_parquet.n_rows(), from_arff.n_rows());
+    for i in 0..from_arff.n_rows() {
+        assert_eq!(from_parquet.row(i), from_arff.row(i));
+    }
+}
@@ -0,0 +1,244 @@
+//! Predictions recorded while running a task, and their export in OpenML's predictions ARFF format
+
+use std::io::{self, Write};
+use std::slice;
+
+use dataset::quote;
+use procedures::Fold;
+
+/// Values that can be written to a predictions file
+pub trait PredictionValue {
+    /// format value for an ARFF file; integer values are treated as index into `labels`, if given.
+    fn to_arff(&self, labels: Option<&[String]>) -> String;
+}
+
+macro_rules! impl_index_prediction_value {
+    ($($t:ty),*) => {
+        $(
+            impl PredictionValue for $t {
+                fn to_arff(&self, labels: Option<&[String]>) -> String {
+                    match labels.and_then(|l| l.get(*self as usize)) {
+                        Some(label) => quote(label),
+                        None => self.to_string(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_index_prediction_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl PredictionValue for f32 {
+    fn to_arff(&self, _labels: Option<&[String]>) -> String {
+        self.to_string()
+    }
+}
+
+impl PredictionValue for f64 {
+    fn to_arff(&self, _labels: Option<&[String]>) -> String {
+        self.to_string()
+    }
+}
+
+impl PredictionValue for String {
+    fn to_arff(&self, _labels: Option<&[String]>) -> String {
+        quote(self)
+    }
+}
+
+impl PredictionValue for &str {
+    fn to_arff(&self, _labels: Option<&[String]>) -> String {
+        quote(self)
+    }
+}
+
+/// A single prediction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prediction<Y> {
+    pub repeat: usize,
+    pub fold: usize,
+    pub row_id: usize,
+    pub predicted: Y,
+    pub truth: Y,
+}
+
+/// All predictions made while running a task
+#[derive(Debug, Clone)]
+pub struct Predictions<Y> {
+    task_id: String,
+    class_labels: Option<Vec<String>>,
+    records: Vec<Prediction<Y>>,
+}
+
+impl<Y> Predictions<Y> {
+    pub(crate) fn new(task_id: &str, class_labels: Option<Vec<String>>) -> Self {
+        Predictions {
+            task_id: task_id.to_owned(),
+            class_labels,
+            records: Vec::new(),
+        }
+    }
+
+    /// record a prediction made for row `row_id` in `fold`
+    pub(crate) fn record(&mut self, fold: &Fold, row_id: usize, truth: &Y, predicted: Y)
+    where
+        Y: Clone,
+    {
+        self.records.push(Prediction {
+            repeat: fold.repeat,
+            fold: fold.fold,
+            row_id,
+            predicted,
+            truth: truth.clone(),
+        })
+    }
+
+    /// class labels of a classification task, or `None` for other tasks
+    pub fn class_labels(&self) -> Option<&[String]> {
+        self.class_labels.as_deref()
+    }
+
+    /// number of recorded predictions
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// `true` if no predictions were recorded
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// iterate over all predictions, in the order they were made
+    pub fn iter(&self) -> slice::Iter<Prediction<Y>> {
+        self.records.iter()
+    }
+}
+
+impl Predictions<usize> {
+    /// get the label of a class index, such as the `predicted` or `truth` value of a prediction
+    pub fn label(&self, index: usize) -> Option<&str> {
+        self.class_labels()?.get(index).map(String::as_str)
+    }
+}
+
+impl<Y: PredictionValue> Predictions<Y> {
+    /// write predictions in OpenML's predictions ARFF format
+    ///
+    /// Classification tasks produce `repeat`, `fold`, `row_id`, one `confidence.<class>` column
+    /// per class, `prediction` and `correct`. Since flows only predict labels, the confidence of
+    /// the predicted class is 1 and all others are 0. Regression tasks produce `repeat`, `fold`,
+    /// `row_id`, `prediction` and `truth`.
+    pub fn write_arff<W: Write>(&self, mut w: W) -> io::Result<()> {
+        writeln!(w, "@RELATION openml_task_{}_predictions", self.task_id)?;
+        writeln!(w)?;
+        writeln!(w, "@ATTRIBUTE repeat NUMERIC")?;
+        writeln!(w, "@ATTRIBUTE fold NUMERIC")?;
+        writeln!(w, "@ATTRIBUTE row_id NUMERIC")?;
+
+        match self.class_labels {
+            Some(ref labels) => {
+                let nominal: Vec<_> = labels.iter().map(|l| quote(l)).collect();
+                let nominal = nominal.join(",");
+                for label in labels {
+                    writeln!(w, "@ATTRIBUTE {} NUMERIC", quote(&format!("confidence.{}", label)))?;
+                }
+                writeln!(w, "@ATTRIBUTE prediction {{{}}}", nominal)?;
+                writeln!(w, "@ATTRIBUTE correct {{{}}}", nominal)?;
+            }
+            None => {
+                writeln!(w, "@ATTRIBUTE prediction NUMERIC")?;
+                writeln!(w, "@ATTRIBUTE truth NUMERIC")?;
+            }
+        }
+
+        writeln!(w)?;
+        writeln!(w, "@DATA")?;
+
+        let labels = self.class_labels();
+        for p in &self.records {
+            write!(w, "{},{},{},", p.repeat, p.fold, p.row_id)?;
+            let predicted = p.predicted.to_arff(labels);
+            if let Some(labels) = labels {
+                for label in labels {
+                    let confidence = if quote(label) == predicted { 1 } else { 0 };
+                    write!(w, "{},", confidence)?;
+                }
+            }
+            writeln!(w, "{},{}", predicted, p.truth.to_arff(labels))?;
+        }
+
+        Ok(())
+    }
+
+    /// predictions in OpenML's predictions ARFF format
+    pub fn to_arff(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_arff(&mut buf).expect("writing to memory failed");
+        String::from_utf8(buf).expect("invalid UTF-8 in predictions")
+    }
+}
+
+impl<'a, Y> IntoIterator for &'a Predictions<Y> {
+    type Item = &'a Prediction<Y>;
+    type IntoIter = slice::Iter<'a, Prediction<Y>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+fn classification_predictions_arff() {
+    let labels = vec!["setosa".to_owned(), "versi color".to_owned()];
+    let mut predictions = Predictions::new("59", Some(labels));
+
+    let fold = Fold::new(0, 1);
+    predictions.record(&fold, 3, &0u8, 0);
+    predictions.record(&fold, 4, &0u8, 1);
+
+    assert_eq!(
+        predictions.to_arff(),
+        "@RELATION openml_task_59_predictions\n\n\
+         @ATTRIBUTE repeat NUMERIC\n\
+         @ATTRIBUTE fold NUMERIC\n\
+         @ATTRIBUTE row_id NUMERIC\n\
+         @ATTRIBUTE confidence.setosa NUMERIC\n\
+         @ATTRIBUTE 'confidence.versi color' NUMERIC\n\
+         @ATTRIBUTE prediction {setosa,'versi color'}\n\
+         @ATTRIBUTE correct {setosa,'versi color'}\n\n\
+         @DATA\n\
+         0,1,3,1,0,setosa,setosa\n\
+         0,1,4,0,1,'versi color',setosa\n"
+    );
+}
+
+#[test]
+fn class_index_labels() {
+    let labels = vec!["setosa".to_owned(), "versicolor".to_owned()];
+    let mut predictions = Predictions::new("59", Some(labels));
+    predictions.record(&Fold::new(0, 0), 0, &1usize, 0);
+
+    let p = predictions.iter().next().unwrap();
+    assert_eq!(predictions.label(p.predicted), Some("setosa"));
+    assert_eq!(predictions.label(p.truth), Some("versicolor"));
+    assert_eq!(predictions.label(2), None);
+}
+
+#[test]
+fn regression_predictions_arff() {
+    let mut predictions = Predictions::new("52948", None);
+    predictions.record(&Fold::new(1, 2), 7, &2.5, 3.0);
+
+    assert_eq!(
+        predictions.to_arff(),
+        "@RELATION openml_task_52948_predictions\n\n\
+         @ATTRIBUTE repeat NUMERIC\n\
+         @ATTRIBUTE fold NUMERIC\n\
+         @ATTRIBUTE row_id NUMERIC\n\
+         @ATTRIBUTE prediction NUMERIC\n\
+         @ATTRIBUTE truth NUMERIC\n\n\
+         @DATA\n\
+         1,2,7,3,2.5\n"
+    );
+}
@@ -12,13 +12,17 @@ pub(crate) trait Procedure {
 /// A single cross-validation fold, consisting of a training set and a testing set
 #[derive(Debug, Clone)]
 pub(crate) struct Fold {
+    pub(crate) repeat: usize,
+    pub(crate) fold: usize,
     pub(crate) trainset: Vec<usize>,
     pub(crate) testset: Vec<usize>,
 }
 
 impl Fold {
-    pub fn new() -> Self {
+    pub fn new(repeat: usize, fold: usize) -> Self {
         Fold {
+            repeat,
+            fold,
             trainset: Vec::new(),
             testset: Vec::new(),
         }
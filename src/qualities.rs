@@ -0,0 +1,284 @@
+//! Data set qualities (meta-features)
+//!
+//! Qualities of data sets on OpenML are loaded with `DataSet::qualities`. Common qualities can
+//! also be computed locally with `compute`, which works for any data set.
+
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use arff::dynamic::Value;
+
+use dataset::{is_missing, DataSet, FeatureType};
+
+/// Data set qualities, such as `NumberOfInstances` or `ClassEntropy`
+///
+/// Qualities are identified by their OpenML name. Accessors are provided for the most common
+/// ones; all others are available through `get`.
+/// ```no_run
+/// # use openml::DataSet;
+/// let data = DataSet::from_openml(61).unwrap();
+/// let qualities = data.qualities().unwrap();
+///
+/// println!("{:?}", qualities.number_of_classes());
+/// println!("{:?}", qualities.get("ClassEntropy"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Qualities {
+    values: BTreeMap<String, f64>,
+}
+
+impl Qualities {
+    /// create an empty set of qualities
+    pub fn new() -> Self {
+        Qualities::default()
+    }
+
+    /// get quality by name
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.get(name).cloned()
+    }
+
+    /// set quality
+    pub fn insert<S: Into<String>>(&mut self, name: S, value: f64) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// number of qualities
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// `true` if there are no qualities
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// iterate over name/value pairs, ordered by name
+    pub fn iter<'a>(&'a self) -> Box<'a + Iterator<Item = (&'a str, f64)>> {
+        Box::new(self.values.iter().map(|(k, &v)| (k.as_str(), v)))
+    }
+
+    /// number of rows
+    pub fn number_of_instances(&self) -> Option<u64> {
+        self.count("NumberOfInstances")
+    }
+
+    /// number of features, including the target
+    pub fn number_of_features(&self) -> Option<u64> {
+        self.count("NumberOfFeatures")
+    }
+
+    /// number of target classes; 0 for regression data sets
+    pub fn number_of_classes(&self) -> Option<u64> {
+        self.count("NumberOfClasses")
+    }
+
+    /// total number of missing values in all features
+    pub fn number_of_missing_values(&self) -> Option<u64> {
+        self.count("NumberOfMissingValues")
+    }
+
+    /// entropy of the target class distribution, in bits
+    pub fn class_entropy(&self) -> Option<f64> {
+        self.get("ClassEntropy")
+    }
+
+    fn count(&self, name: &str) -> Option<u64> {
+        self.get(name).map(|v| v as u64)
+    }
+}
+
+impl FromIterator<(String, f64)> for Qualities {
+    fn from_iter<I: IntoIterator<Item = (String, f64)>>(iter: I) -> Self {
+        Qualities {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Compute common qualities of a data set. Names and definitions follow OpenML: the target
+/// counts as feature, while row IDs and ignored attributes do not. Skewness and kurtosis are
+/// averaged over numeric features other than the target.
+///
+/// Sparse feature columns count as numeric unless the data set's feature information declares
+/// them nominal. They are not included in skewness and kurtosis.
+pub fn compute(data: &DataSet) -> Qualities {
+    let arff = data.data();
+    let excluded = data.excluded_columns();
+    let columns: Vec<_> = (0..arff.n_cols())
+        .map(|i| arff.col(i))
+        .filter(|col| !excluded.contains(&col.name()))
+        .collect();
+
+    let n_instances = arff.n_rows() as f64;
+    let n_sparse = data.sparse_features().map_or(0, |sparse| sparse.n_cols());
+    let n_features = (columns.len() + n_sparse) as f64;
+
+    let mut n_missing = 0;
+    let mut n_numeric = 0;
+    let mut n_symbolic = 0;
+    let mut skewness = vec![];
+    let mut kurtosis = vec![];
+
+    for col in &columns {
+        let values: Vec<_> = (0..col.len()).map(|i| col.item(i)).collect();
+        n_missing += values.iter().filter(|v| is_missing(v)).count();
+
+        match data.column_type(col) {
+            FeatureType::Numeric => {
+                n_numeric += 1;
+                if Some(col.name()) != data.target() {
+                    let x: Vec<_> = values.iter().filter_map(|v| v.as_f64().ok()).collect();
+                    if let Some((s, k)) = moments(&x) {
+                        skewness.push(s);
+                        kurtosis.push(k);
+                    }
+                }
+            }
+            FeatureType::Nominal => n_symbolic += 1,
+            FeatureType::String | FeatureType::Date => {}
+        }
+    }
+
+    if let Some(sparse) = data.sparse_features() {
+        n_missing += sparse
+            .rows()
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|&&(_, v)| v.is_nan())
+            .count();
+
+        for name in sparse.names() {
+            let info = data.features().iter().find(|f| f.name == *name);
+            match info.map(|f| f.data_type) {
+                Some(FeatureType::Nominal) => n_symbolic += 1,
+                _ => n_numeric += 1,
+            }
+        }
+    }
+
+    let mut qualities = Qualities::new();
+    qualities.insert("NumberOfInstances", n_instances);
+    qualities.insert("NumberOfFeatures", n_features);
+    qualities.insert("NumberOfMissingValues", n_missing as f64);
+    qualities.insert("NumberOfNumericFeatures", n_numeric as f64);
+    qualities.insert("NumberOfSymbolicFeatures", n_symbolic as f64);
+
+    if n_instances > 0.0 && n_features > 0.0 {
+        qualities.insert(
+            "PercentageOfMissingValues",
+            100.0 * n_missing as f64 / (n_instances * n_features),
+        );
+    }
+
+    if n_features > 0.0 {
+        qualities.insert("PercentageOfNumericFeatures", 100.0 * n_numeric as f64 / n_features);
+        qualities.insert("PercentageOfSymbolicFeatures", 100.0 * n_symbolic as f64 / n_features);
+    }
+
+    if !skewness.is_empty() {
+        qualities.insert("MeanSkewnessOfNumericAtts", mean(&skewness));
+        qualities.insert("MeanKurtosisOfNumericAtts", mean(&kurtosis));
+    }
+
+    match data.target_categories() {
+        Some(categories) => {
+            let target = arff.col_by_name(data.target().unwrap());
+            let mut counts = vec![0usize; categories.len()];
+            for i in 0..target.len() {
+                if let Value::Nominal(c, _) = target.item(i) {
+                    counts[c] += 1;
+                }
+            }
+
+            let total: usize = counts.iter().sum();
+            let entropy = counts
+                .iter()
+                .filter(|&&c| c > 0)
+                .map(|&c| c as f64 / total as f64)
+                .map(|p| -p * p.log2())
+                .sum();
+
+            qualities.insert("NumberOfClasses", categories.len() as f64);
+            qualities.insert("ClassEntropy", entropy);
+        }
+        None => qualities.insert("NumberOfClasses", 0.0),
+    }
+
+    qualities
+}
+
+fn mean(x: &[f64]) -> f64 {
+    x.iter().sum::<f64>() / x.len() as f64
+}
+
+/// skewness and excess kurtosis of a sample, or `None` if the sample has no variance
+fn moments(x: &[f64]) -> Option<(f64, f64)> {
+    if x.is_empty() {
+        return None;
+    }
+
+    let mu = mean(x);
+    let central = |k: i32| x.iter().map(|xi| (xi - mu).powi(k)).sum::<f64>() / x.len() as f64;
+
+    let m2 = central(2);
+    if m2 <= 0.0 {
+        return None;
+    }
+
+    Some((central(3) / m2.powf(1.5), central(4) / (m2 * m2) - 3.0))
+}
+
+#[test]
+fn compute_qualities() {
+    use arff::dynamic::DataSet as ArffDataSet;
+
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE color {red, green, blue}\n\
+         @ATTRIBUTE y {a, b}\n\
+         @DATA\n\
+         1, red, a\n\
+         2, ?, a\n\
+         3, green, b\n\
+         10, red, b\n",
+    ).unwrap();
+
+    let qualities = compute(&DataSet::from_arff(arff, Some("y")));
+
+    assert_eq!(qualities.number_of_instances(), Some(4));
+    assert_eq!(qualities.number_of_features(), Some(3));
+    assert_eq!(qualities.number_of_classes(), Some(2));
+    assert_eq!(qualities.number_of_missing_values(), Some(1));
+    assert_eq!(qualities.class_entropy(), Some(1.0));
+    assert_eq!(qualities.get("PercentageOfMissingValues"), Some(100.0 / 12.0));
+    assert_eq!(qualities.get("NumberOfNumericFeatures"), Some(1.0));
+    assert_eq!(qualities.get("NumberOfSymbolicFeatures"), Some(2.0));
+    assert!(qualities.get("MeanSkewnessOfNumericAtts").unwrap() > 0.0);
+    assert!(qualities.get("MeanKurtosisOfNumericAtts").is_some());
+}
+
+#[test]
+fn compute_sparse_qualities() {
+    use sparse;
+
+    let input = "@RELATION test\n\
+                 @ATTRIBUTE w1 NUMERIC\n\
+                 @ATTRIBUTE w2 NUMERIC\n\
+                 @ATTRIBUTE class {neg, pos}\n\
+                 @DATA\n\
+                 {0 1, 2 pos}\n\
+                 {1 ?}\n";
+    let (arff, features) = sparse::parse(input, &["class"]).unwrap();
+    let mut data = DataSet::from_arff(arff, Some("class"));
+    data.sparse = Some(features);
+
+    let qualities = compute(&data);
+
+    assert_eq!(qualities.number_of_instances(), Some(2));
+    assert_eq!(qualities.number_of_features(), Some(3));
+    assert_eq!(qualities.number_of_missing_values(), Some(1));
+    assert_eq!(qualities.get("NumberOfNumericFeatures"), Some(2.0));
+    assert_eq!(qualities.get("NumberOfSymbolicFeatures"), Some(1.0));
+}
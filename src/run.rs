@@ -0,0 +1,110 @@
+//! Runs are the results of applying a flow to a task, which can be published on OpenML
+
+use xml::{push_element, OML_NAMESPACE};
+
+/// A run of a flow on a task
+///
+/// The run consists of a description and the predictions made on the task's test sets, formatted
+/// as OpenML predictions ARFF file.
+#[derive(Debug, Clone)]
+pub struct Run {
+    task_id: u32,
+    flow_id: u32,
+    setup_string: Option<String>,
+    parameters: Vec<(String, String)>,
+    tags: Vec<String>,
+    predictions: String,
+}
+
+impl Run {
+    /// create a new run of flow `flow_id` on task `task_id`
+    pub fn new(task_id: u32, flow_id: u32, predictions: String) -> Self {
+        Run {
+            task_id,
+            flow_id,
+            setup_string: None,
+            parameters: Vec::new(),
+            tags: Vec::new(),
+            predictions,
+        }
+    }
+
+    /// set a free-form description of the setup
+    pub fn with_setup_string<S: Into<String>>(mut self, setup: S) -> Self {
+        self.setup_string = Some(setup.into());
+        self
+    }
+
+    /// record the value of a flow parameter
+    pub fn with_parameter<S: Into<String>, T: ToString>(mut self, name: S, value: T) -> Self {
+        self.parameters.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// add a tag
+    pub fn with_tag<S: Into<String>>(mut self, tag: S) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// get task ID
+    pub fn task_id(&self) -> u32 {
+        self.task_id
+    }
+
+    /// get flow ID
+    pub fn flow_id(&self) -> u32 {
+        self.flow_id
+    }
+
+    /// get predictions in ARFF format
+    pub fn predictions(&self) -> &str {
+        &self.predictions
+    }
+
+    /// the run description, as expected by OpenML's run upload
+    pub fn description_xml(&self) -> String {
+        let mut xml = format!("<oml:run xmlns:oml=\"{}\">\n", OML_NAMESPACE);
+
+        push_element(&mut xml, 1, "task_id", &self.task_id.to_string());
+        push_element(&mut xml, 1, "flow_id", &self.flow_id.to_string());
+
+        if let Some(ref setup) = self.setup_string {
+            push_element(&mut xml, 1, "setup_string", setup);
+        }
+
+        for (name, value) in &self.parameters {
+            xml.push_str("  <oml:parameter_setting>\n");
+            push_element(&mut xml, 2, "name", name);
+            push_element(&mut xml, 2, "value", value);
+            xml.push_str("  </oml:parameter_setting>\n");
+        }
+
+        for tag in &self.tags {
+            push_element(&mut xml, 1, "tag", tag);
+        }
+
+        xml.push_str("</oml:run>\n");
+        xml
+    }
+}
+
+#[test]
+fn run_description() {
+    let run = Run::new(59, 7, String::new())
+        .with_parameter("k", 3)
+        .with_tag("rust");
+
+    assert_eq!(
+        run.description_xml(),
+        "<oml:run xmlns:oml=\"http://openml.org/openml\">\n\
+         \x20 <oml:task_id>59</oml:task_id>\n\
+         \x20 <oml:flow_id>7</oml:flow_id>\n\
+         \x20 <oml:parameter_setting>\n\
+         \x20   <oml:name>k</oml:name>\n\
+         \x20   <oml:value>3</oml:value>\n\
+         \x20 </oml:parameter_setting>\n\
+         \x20 <oml:tag>rust</oml:tag>\n\
+         </oml:run>\n"
+    );
+}
@@ -0,0 +1,333 @@
+//! Support for data sets in sparse ARFF format
+//!
+//! The `arff` crate only reads dense ARFF, so sparse files are parsed here. Feature columns are
+//! kept in sparse rows, while the target and other non-feature columns are converted to a dense
+//! ARFF data set.
+
+use std::f64;
+
+use arff::dynamic::DataSet as ArffDataSet;
+
+use dataset::{quote, unquote};
+use error::{Error, Result};
+
+/// Sparse feature rows of a data set
+///
+/// Each row is a list of `(column, value)` pairs in ascending column order; omitted values are
+/// zero. Nominal features are represented by the index of their level, and missing values by
+/// `NaN`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseFeatures {
+    names: Vec<String>,
+    rows: Vec<Vec<(usize, f64)>>,
+}
+
+impl SparseFeatures {
+    /// names of the feature columns
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// number of feature columns
+    pub fn n_cols(&self) -> usize {
+        self.names.len()
+    }
+
+    /// number of rows
+    pub fn n_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// get a single row
+    pub fn row(&self, i: usize) -> &[(usize, f64)] {
+        &self.rows[i]
+    }
+
+    /// get all rows
+    pub fn rows(&self) -> &[Vec<(usize, f64)>] {
+        &self.rows
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AttributeType {
+    Numeric,
+    Nominal(Vec<String>),
+    String,
+    Date,
+}
+
+#[derive(Debug)]
+struct Attribute {
+    name: String,
+    kind: AttributeType,
+}
+
+/// Parse a sparse ARFF file. Columns listed in `dense` are returned as dense ARFF data set; all
+/// other numeric and nominal columns become sparse features. Other string or date columns are
+/// dropped.
+pub(crate) fn parse(input: &str, dense: &[&str]) -> Result<(ArffDataSet, SparseFeatures)> {
+    let mut relation = String::from("data");
+    let mut attributes = Vec::new();
+    let mut lines = input.lines().map(str::trim);
+
+    for line in &mut lines {
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+
+        let lower = line.to_lowercase();
+        if lower.starts_with("@relation") {
+            relation = unquote(line["@relation".len()..].trim());
+        } else if lower.starts_with("@attribute") {
+            attributes.push(parse_attribute(line["@attribute".len()..].trim())?);
+        } else if lower.starts_with("@data") {
+            break;
+        } else {
+            return Err(parse_error(format!("unexpected header line: {}", line)));
+        }
+    }
+
+    let mut feature_index = Vec::with_capacity(attributes.len());
+    let mut names = Vec::new();
+    let mut dense_index = Vec::with_capacity(attributes.len());
+    let mut dense_attrs = Vec::new();
+    for attr in &attributes {
+        if dense.contains(&attr.name.as_str()) {
+            feature_index.push(None);
+            dense_index.push(Some(dense_attrs.len()));
+            dense_attrs.push(attr);
+        } else if attr.kind == AttributeType::String || attr.kind == AttributeType::Date {
+            feature_index.push(None);
+            dense_index.push(None);
+        } else {
+            feature_index.push(Some(names.len()));
+            dense_index.push(None);
+            names.push(attr.name.clone());
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut dense_rows = Vec::new();
+    for line in lines {
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+
+        let mut row = Vec::new();
+        let mut dense_row: Vec<_> = dense_attrs.iter().map(|a| default_value(&a.kind)).collect();
+
+        for entry in split_list(sparse_entries(line)?) {
+            let split = entry.find(char::is_whitespace).unwrap_or(entry.len());
+            let (index, value) = (&entry[..split], entry[split..].trim());
+            let index: usize = index
+                .parse()
+                .map_err(|_| parse_error(format!("invalid sparse entry: {}", entry)))?;
+
+            let attr = attributes
+                .get(index)
+                .ok_or_else(|| parse_error(format!("column {} does not exist", index)))?;
+
+            if let Some(f) = feature_index[index] {
+                row.push((f, feature_value(attr, value)?));
+            } else if let Some(d) = dense_index[index] {
+                dense_row[d] = match attr.kind {
+                    AttributeType::Numeric => value.to_owned(),
+                    _ if value == "?" => value.to_owned(),
+                    _ => quote(&unquote(value)),
+                };
+            }
+        }
+
+        rows.push(row);
+        dense_rows.push(dense_row);
+    }
+
+    let dset = if dense_attrs.is_empty() {
+        // a data set without columns still needs to know the number of rows
+        let placeholder = format!(
+            "@RELATION r\n@ATTRIBUTE _ NUMERIC\n@DATA\n{}",
+            "0\n".repeat(rows.len())
+        );
+        let (dset, _) = ArffDataSet::from_str(&placeholder)?.split_one("_");
+        dset
+    } else {
+        ArffDataSet::from_str(&dense_arff(&relation, &dense_attrs, &dense_rows))?
+    };
+
+    Ok((dset, SparseFeatures { names, rows }))
+}
+
+fn dense_arff(relation: &str, attributes: &[&Attribute], rows: &[Vec<String>]) -> String {
+    let mut arff = format!("@RELATION {}\n", quote(relation));
+    for attr in attributes {
+        let kind = match attr.kind {
+            AttributeType::Numeric => "NUMERIC".to_owned(),
+            AttributeType::Nominal(ref levels) => {
+                let levels: Vec<_> = levels.iter().map(|l| quote(l)).collect();
+                format!("{{{}}}", levels.join(","))
+            }
+            AttributeType::String | AttributeType::Date => "STRING".to_owned(),
+        };
+        arff.push_str(&format!("@ATTRIBUTE {} {}\n", quote(&attr.name), kind));
+    }
+
+    arff.push_str("@DATA\n");
+    for row in rows {
+        arff.push_str(&row.join(","));
+        arff.push('\n');
+    }
+    arff
+}
+
+fn parse_error(msg: String) -> Error {
+    Error::ParseError(msg)
+}
+
+fn parse_attribute(s: &str) -> Result<Attribute> {
+    let (name, rest) = match s.chars().next() {
+        Some(q) if q == '\'' || q == '"' => {
+            let end = closing_quote(s)
+                .ok_or_else(|| parse_error(format!("unterminated attribute name: {}", s)))?;
+            (unquote(&s[..end + 1]), s[end + 1..].trim())
+        }
+        _ => {
+            let end = s.find(char::is_whitespace).unwrap_or(s.len());
+            (s[..end].to_owned(), s[end..].trim())
+        }
+    };
+
+    let lower = rest.to_lowercase();
+    let kind = if rest.starts_with('{') && rest.ends_with('}') {
+        AttributeType::Nominal(
+            split_list(&rest[1..rest.len() - 1])
+                .into_iter()
+                .map(unquote)
+                .collect(),
+        )
+    } else if lower == "numeric" || lower == "real" || lower == "integer" {
+        AttributeType::Numeric
+    } else if lower == "string" {
+        AttributeType::String
+    } else if lower.starts_with("date") {
+        AttributeType::Date
+    } else {
+        return Err(parse_error(format!("unsupported attribute type: {}", rest)));
+    };
+
+    Ok(Attribute { name, kind })
+}
+
+/// the value of an omitted entry
+fn default_value(kind: &AttributeType) -> String {
+    match *kind {
+        AttributeType::Nominal(ref levels) => {
+            levels.first().map(|l| quote(l)).unwrap_or_default()
+        }
+        AttributeType::String | AttributeType::Date => "''".to_owned(),
+        AttributeType::Numeric => "0".to_owned(),
+    }
+}
+
+fn feature_value(attr: &Attribute, value: &str) -> Result<f64> {
+    if value == "?" {
+        return Ok(f64::NAN);
+    }
+
+    match attr.kind {
+        AttributeType::Numeric => value
+            .parse()
+            .map_err(|_| parse_error(format!("invalid value for {}: {}", attr.name, value))),
+        AttributeType::Nominal(ref levels) => {
+            let value = unquote(value);
+            levels
+                .iter()
+                .position(|l| *l == value)
+                .map(|i| i as f64)
+                .ok_or_else(|| parse_error(format!("invalid level for {}: {}", attr.name, value)))
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// get the entries inside the braces of a sparse row, ignoring an optional instance weight
+fn sparse_entries(line: &str) -> Result<&str> {
+    if !line.starts_with('{') {
+        return Err(parse_error(format!("expected sparse row: {}", line)));
+    }
+
+    let mut open_quote = None;
+    for (i, c) in line.char_indices().skip(1) {
+        match (open_quote, c) {
+            (Some(q), c) if c == q => open_quote = None,
+            (None, '\'') | (None, '"') => open_quote = Some(c),
+            (None, '}') => return Ok(&line[1..i]),
+            _ => {}
+        }
+    }
+
+    Err(parse_error(format!("unterminated sparse row: {}", line)))
+}
+
+/// split a comma separated list, respecting quotes
+fn split_list(s: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut open_quote = None;
+    for (i, c) in s.char_indices() {
+        match (open_quote, c) {
+            (Some(q), c) if c == q => open_quote = None,
+            (None, '\'') | (None, '"') => open_quote = Some(c),
+            (None, ',') => {
+                items.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(s[start..].trim());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// position of the quote that closes the quote at the start of `s`
+fn closing_quote(s: &str) -> Option<usize> {
+    let q = s.chars().next()?;
+    s.char_indices().skip(1).find(|&(_, c)| c == q).map(|(i, _)| i)
+}
+
+#[test]
+fn parse_sparse_arff() {
+    use dataset::DataSet;
+
+    let (dense, sparse) = parse(
+        "% a comment\n\
+         @RELATION 'sparse test'\n\
+         @ATTRIBUTE id NUMERIC\n\
+         @ATTRIBUTE w1 NUMERIC\n\
+         @ATTRIBUTE 'w 2' REAL\n\
+         @ATTRIBUTE kind {x, 'y z'}\n\
+         @ATTRIBUTE note STRING\n\
+         @ATTRIBUTE class {'neg', \"it's\"}\n\
+         @DATA\n\
+         {0 1, 1 2.5, 5 \"it's\"}\n\
+         {0 2, 2 ?, 3 'y z', 4 'a, b'}\n\
+         {0 3}, {2}\n",
+        &["class", "id"],
+    ).unwrap();
+
+    assert_eq!(sparse.names(), &["w1", "w 2", "kind"]);
+    assert_eq!(sparse.row(0), &[(0, 2.5)]);
+    assert_eq!(sparse.row(1)[1], (2, 1.0));
+    assert!(sparse.row(1)[0].1.is_nan());
+    assert!(sparse.row(2).is_empty());
+
+    assert_eq!(dense.col_names().collect::<Vec<_>>(), vec!["id", "class"]);
+    assert_eq!(dense.n_rows(), 3);
+    assert_eq!(dense.item(1, 0).as_f64().unwrap(), 2.0);
+    assert_eq!(dense.item(0, 1).as_u8().unwrap(), 1);
+    assert_eq!(dense.item(2, 1).as_u8().unwrap(), 0);
+
+    let data = DataSet::from_arff(dense, Some("class"));
+    assert_eq!(data.target_categories(), Some(vec!["neg".to_owned(), "it's".to_owned()]));
+}
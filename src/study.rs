@@ -0,0 +1,67 @@
+//! Studies and benchmark suites are curated collections of tasks and data sets
+
+use error::Result;
+use openml_api::FromOpenml;
+
+/// A study or benchmark suite, such as OpenML-CC18
+///
+/// Tasks are loaded lazily:
+/// ```no_run
+/// # use openml::{BenchmarkSuite, SupervisedClassification};
+/// let suite = BenchmarkSuite::from_openml("OpenML-CC18").unwrap();
+///
+/// for task in suite.tasks::<SupervisedClassification>() {
+///     let task = task.unwrap();
+///     println!("{}", task.name());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Study {
+    pub(crate) id: u32,
+    pub(crate) alias: Option<String>,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) task_ids: Vec<u32>,
+    pub(crate) dataset_ids: Vec<u32>,
+}
+
+/// Benchmark suites are studies that consist of tasks
+pub type BenchmarkSuite = Study;
+
+impl Study {
+    /// get study ID
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// get study alias, such as `"OpenML-CC18"`
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    /// get study name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// get study description
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// get IDs of the tasks in the study
+    pub fn task_ids(&self) -> &[u32] {
+        &self.task_ids
+    }
+
+    /// get IDs of the data sets in the study
+    pub fn dataset_ids(&self) -> &[u32] {
+        &self.dataset_ids
+    }
+
+    /// iterate over the study's tasks. Each task is loaded when the iterator reaches it.
+    pub fn tasks<'a, T: 'a + FromOpenml>(&'a self) -> Box<'a + Iterator<Item = Result<T>>> {
+        let iter = self.task_ids.iter().map(|&id| T::from_openml(id));
+        Box::new(iter)
+    }
+}
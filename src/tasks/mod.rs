@@ -1,15 +1,100 @@
 //! Implementations of specific OpenML task types
 
+mod report;
+pub(crate) mod runner;
 mod supervised_classification;
 mod supervised_regression;
 
+use std::str::FromStr;
+
 use serde::de::DeserializeOwned;
 
+pub use self::report::{FoldError, FoldFailure, RunReport};
+pub use self::runner::MissingMask;
 pub use self::supervised_classification::SupervisedClassification;
 pub use self::supervised_regression::SupervisedRegression;
 
+use error::{Error, Result};
 use measure_accumulator::MeasureAccumulator;
 
+/// OpenML task types
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TaskType {
+    SupervisedClassification,
+    SupervisedRegression,
+    LearningCurve,
+    SupervisedDatastreamClassification,
+    Clustering,
+    MachineLearningChallenge,
+    SurvivalAnalysis,
+    SubgroupDiscovery,
+}
+
+impl TaskType {
+    /// get task type from its OpenML ID
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            1 => Some(TaskType::SupervisedClassification),
+            2 => Some(TaskType::SupervisedRegression),
+            3 => Some(TaskType::LearningCurve),
+            4 => Some(TaskType::SupervisedDatastreamClassification),
+            5 => Some(TaskType::Clustering),
+            6 => Some(TaskType::MachineLearningChallenge),
+            7 => Some(TaskType::SurvivalAnalysis),
+            8 => Some(TaskType::SubgroupDiscovery),
+            _ => None,
+        }
+    }
+
+    /// get the OpenML ID of the task type
+    pub fn id(&self) -> u32 {
+        match *self {
+            TaskType::SupervisedClassification => 1,
+            TaskType::SupervisedRegression => 2,
+            TaskType::LearningCurve => 3,
+            TaskType::SupervisedDatastreamClassification => 4,
+            TaskType::Clustering => 5,
+            TaskType::MachineLearningChallenge => 6,
+            TaskType::SurvivalAnalysis => 7,
+            TaskType::SubgroupDiscovery => 8,
+        }
+    }
+}
+
+/// Status of a task on OpenML
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TaskStatus {
+    #[default]
+    Active,
+    Deactivated,
+    InPreparation,
+}
+
+impl FromStr for TaskStatus {
+    type Err = Error;
+
+    /// parse status as used by the OpenML API
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "active" => Ok(TaskStatus::Active),
+            "deactivated" => Ok(TaskStatus::Deactivated),
+            "in_preparation" | "in preparation" => Ok(TaskStatus::InPreparation),
+            _ => Err(Error::ParseError(format!("unknown task status '{}'", s))),
+        }
+    }
+}
+
+impl TaskStatus {
+    /// status as used by the OpenML API
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            TaskStatus::Active => "active",
+            TaskStatus::Deactivated => "deactivated",
+            TaskStatus::InPreparation => "in_preparation",
+        }
+    }
+}
+
 pub trait Task {
     /// get task ID
     fn id(&self) -> &str;
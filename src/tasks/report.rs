@@ -0,0 +1,96 @@
+//! Results of runs with flows that can fail on individual folds
+
+use std::any::Any;
+use std::fmt;
+
+/// Reason why a flow failed on a fold
+#[derive(Debug, Clone, PartialEq)]
+pub enum FoldError<E> {
+    /// the flow returned an error
+    Error(E),
+
+    /// the flow panicked; contains the panic message
+    Panic(String),
+}
+
+impl<E> FoldError<E> {
+    /// convert the payload of a caught panic
+    pub(crate) fn from_panic(payload: Box<Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(s) => *s,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(s) => (*s).to_owned(),
+                Err(_) => "unknown panic".to_owned(),
+            },
+        };
+        FoldError::Panic(message)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for FoldError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FoldError::Error(ref e) => write!(f, "{}", e),
+            FoldError::Panic(ref msg) => write!(f, "panic: {}", msg),
+        }
+    }
+}
+
+/// A fold on which the flow failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoldFailure<E> {
+    pub repeat: usize,
+    pub fold: usize,
+    pub error: FoldError<E>,
+}
+
+/// Result of running a task with a flow that can fail
+///
+/// The measure is computed from the folds on which the flow succeeded; the remaining folds are
+/// listed in `failures()`.
+#[derive(Debug)]
+pub struct RunReport<M, E> {
+    measure: M,
+    n_folds: usize,
+    failures: Vec<FoldFailure<E>>,
+}
+
+impl<M, E> RunReport<M, E> {
+    pub(crate) fn new(measure: M, n_folds: usize, failures: Vec<FoldFailure<E>>) -> Self {
+        RunReport {
+            measure,
+            n_folds,
+            failures,
+        }
+    }
+
+    /// get the measure of the successful folds
+    pub fn measure(&self) -> &M {
+        &self.measure
+    }
+
+    /// get the measure of the successful folds, discarding the failures
+    pub fn into_measure(self) -> M {
+        self.measure
+    }
+
+    /// get the failed folds, in the order in which they were run
+    pub fn failures(&self) -> &[FoldFailure<E>] {
+        &self.failures
+    }
+
+    /// get the total number of folds, including failed ones
+    pub fn n_folds(&self) -> usize {
+        self.n_folds
+    }
+
+    /// get the number of folds on which the flow succeeded
+    pub fn n_succeeded(&self) -> usize {
+        self.n_folds - self.failures.len()
+    }
+
+    /// `true` if the flow succeeded on all folds
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
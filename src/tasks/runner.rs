@@ -0,0 +1,366 @@
+//! Functionality shared by the task runners
+
+use arff;
+use arff::dynamic::de::from_dataset;
+use arff::dynamic::{DataSet as ArffDataSet, Value};
+use serde::de::DeserializeOwned;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use dataset::{is_missing, DataSet};
+use procedures::{Fold, Procedure};
+
+use super::report::{FoldError, FoldFailure};
+
+/// Rows with missing feature values in one fold
+///
+/// `train` and `test` are aligned with the rows passed to the flow; e.g. `test[i]` is `true` if
+/// the `i`-th test row contains a missing value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingMask {
+    pub repeat: usize,
+    pub fold: usize,
+    pub train: Vec<bool>,
+    pub test: Vec<bool>,
+}
+
+/// Split data set into features and target, and deserialize both. Also returns the number of
+/// feature columns.
+///
+/// Panics with a descriptive message if the target has missing values, or if features contain
+/// missing values that can not be represented by `X`.
+pub(crate) fn load_xy<X, Y>(data: &DataSet, task_type: &str) -> (Vec<X>, Vec<Y>, usize)
+where
+    X: DeserializeOwned,
+    Y: DeserializeOwned,
+{
+    require_dense(data);
+    let (dx, dy) = split(data, task_type);
+    (load_features(&dx), load_target(&dy), dx.n_cols())
+}
+
+/// Like `load_xy`, but the nominal target is converted to class indices, following the order in
+/// which the classes are declared.
+pub(crate) fn load_x_classes<X>(data: &DataSet, task_type: &str) -> (Vec<X>, Vec<usize>, usize)
+where
+    X: DeserializeOwned,
+{
+    require_dense(data);
+    let (dx, dy) = split(data, task_type);
+
+    let target = dy.col(0);
+    let y = (0..target.len())
+        .map(|i| match target.item(i) {
+            Value::Nominal(c, _) => c,
+            _ => panic!("target column '{}' is not nominal", target.name()),
+        })
+        .collect();
+
+    (load_features(&dx), y, dx.n_cols())
+}
+
+/// Deserialize the target column only, with the same checks as `load_xy`.
+pub(crate) fn load_y<Y: DeserializeOwned>(data: &DataSet, task_type: &str) -> Vec<Y> {
+    let (_, dy) = split(data, task_type);
+    load_target(&dy)
+}
+
+/// Panic if the data set's features are sparse, and thus not available to dense runners.
+pub(crate) fn require_dense(data: &DataSet) {
+    if data.is_sparse() {
+        panic!("the data set is sparse; use `run_sparse` to receive sparse feature rows");
+    }
+}
+
+fn split(data: &DataSet, task_type: &str) -> (ArffDataSet, ArffDataSet) {
+    let (dx, dy) = data
+        .clone_split()
+        .unwrap_or_else(|| panic!("{} requires a target column", task_type));
+
+    let target = dy.col(0);
+    let n_missing = (0..target.len()).filter(|&i| is_missing(&target.item(i))).count();
+    if n_missing > 0 {
+        panic!(
+            "{} requires a target without missing values, but target column '{}' is missing in \
+             {} rows",
+            task_type,
+            target.name(),
+            n_missing
+        );
+    }
+
+    (dx, dy)
+}
+
+fn load_features<X: DeserializeOwned>(dx: &ArffDataSet) -> Vec<X> {
+    from_dataset(dx).unwrap_or_else(|e| match e {
+        arff::Error::UnexpectedMissingValue => panic!(
+            "features contain missing values; use `Option<_>` feature types to receive them"
+        ),
+        e => panic!("could not deserialize features: {}", e),
+    })
+}
+
+fn load_target<Y: DeserializeOwned>(dy: &ArffDataSet) -> Vec<Y> {
+    from_dataset(dy).unwrap_or_else(|e| panic!("could not deserialize target: {}", e))
+}
+
+/// Apply a row mask to the training and testing sets of each fold.
+pub(crate) fn fold_masks(procedure: &Procedure, rows: &[bool]) -> Vec<MissingMask> {
+    procedure
+        .iter()
+        .map(|fold| MissingMask {
+            repeat: fold.repeat,
+            fold: fold.fold,
+            train: fold.trainset.iter().map(|&i| rows[i]).collect(),
+            test: fold.testset.iter().map(|&i| rows[i]).collect(),
+        })
+        .collect()
+}
+
+/// Run the flow on each fold of the estimation procedure. `rows` maps row indices to feature rows.
+/// Every prediction is passed to `on_prediction`, together with the fold, the row index and the
+/// known target value.
+pub(crate) fn run_folds<'a, R, Y, G, F, I, P>(
+    procedure: &Procedure,
+    rows: G,
+    y: &'a [Y],
+    mut flow: F,
+    mut on_prediction: P,
+) where
+    R: 'a + ?Sized,
+    G: Fn(usize) -> &'a R,
+    F: FnMut(&mut Iterator<Item = (&R, &Y)>, &mut Iterator<Item = &R>) -> I,
+    I: IntoIterator<Item = Y>,
+    P: FnMut(&Fold, usize, &Y, Y),
+{
+    for fold in procedure.iter() {
+        let predictit = {
+            let mut train = fold.trainset.iter().map(|&i| (rows(i), &y[i]));
+
+            let mut test = fold.testset.iter().map(|&i| rows(i));
+
+            flow(&mut train, &mut test)
+        };
+
+        for (&i, pred) in fold.testset.iter().zip(predictit) {
+            on_prediction(fold, i, &y[i], pred);
+        }
+    }
+}
+
+/// Like `run_folds`, but the flow may fail. Errors returned by the flow and panics while the
+/// flow trains or predicts are caught, and the fold is skipped. Returns the number of folds and
+/// the failed folds.
+pub(crate) fn run_folds_fallible<'a, R, Y, E, G, F, P>(
+    procedure: &Procedure,
+    rows: G,
+    y: &'a [Y],
+    mut flow: F,
+    mut on_prediction: P,
+) -> (usize, Vec<FoldFailure<E>>)
+where
+    R: 'a + ?Sized,
+    G: Fn(usize) -> &'a R,
+    F: FnMut(&mut Iterator<Item = (&R, &Y)>, &mut Iterator<Item = &R>)
+        -> Result<Box<Iterator<Item = Y>>, E>,
+    P: FnMut(&Fold, usize, &Y, Y),
+{
+    let mut n_folds = 0;
+    let mut failures = vec![];
+
+    for fold in procedure.iter() {
+        n_folds += 1;
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut train = fold.trainset.iter().map(|&i| (rows(i), &y[i]));
+
+            let mut test = fold.testset.iter().map(|&i| rows(i));
+
+            flow(&mut train, &mut test).map(|predictions| predictions.collect::<Vec<_>>())
+        }));
+
+        let error = match result {
+            Ok(Ok(predictions)) => {
+                for (&i, pred) in fold.testset.iter().zip(predictions) {
+                    on_prediction(fold, i, &y[i], pred);
+                }
+                continue;
+            }
+            Ok(Err(e)) => FoldError::Error(e),
+            Err(payload) => FoldError::from_panic(payload),
+        };
+
+        failures.push(FoldFailure {
+            repeat: fold.repeat,
+            fold: fold.fold,
+            error,
+        });
+    }
+
+    (n_folds, failures)
+}
+
+#[test]
+fn run_all_folds() {
+    use procedures::FrozenSets;
+
+    let mut fold0 = Fold::new(0, 0);
+    fold0.trainset = vec![0, 1];
+    fold0.testset = vec![2, 3];
+
+    let mut fold1 = Fold::new(0, 1);
+    fold1.trainset = vec![2, 3];
+    fold1.testset = vec![0, 1];
+
+    let procedure = FrozenSets {
+        folds: vec![vec![fold0, fold1]],
+    };
+
+    let x = [10, 11, 12, 13];
+    let y = [0, 1, 2, 3];
+
+    let mut results = vec![];
+    run_folds(
+        &procedure,
+        |i| &x[i],
+        &y,
+        |train, test| {
+            let offset: i32 = train.map(|(x, y)| x - y).sum();
+            let pred: Vec<_> = test.map(|x| x - offset / 2).collect();
+            Box::new(pred.into_iter())
+        },
+        |fold, i, known, pred| results.push((fold.fold, i, *known, pred)),
+    );
+
+    assert_eq!(results, vec![(0, 2, 2, 2), (0, 3, 3, 3), (1, 0, 0, 0), (1, 1, 1, 1)]);
+}
+
+#[cfg(test)]
+fn missing_data() -> DataSet {
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE a NUMERIC\n\
+         @ATTRIBUTE b NUMERIC\n\
+         @ATTRIBUTE y NUMERIC\n\
+         @DATA\n\
+         1, ?, 1\n\
+         2, 3, ?\n",
+    ).unwrap();
+
+    DataSet::from_arff(arff, Some("b"))
+}
+
+#[test]
+fn optional_features() {
+    let data = missing_data().with_target("a");
+    let (x, y, n_cols) = load_xy::<Option<f64>, f64>(&data, "test");
+
+    assert_eq!(n_cols, 2);
+    assert_eq!(x, vec![None, Some(1.0), Some(3.0), None]);
+    assert_eq!(y, vec![1.0, 2.0]);
+}
+
+#[test]
+#[should_panic(expected = "target column 'b' is missing in 1 rows")]
+fn missing_target() {
+    load_xy::<Option<f64>, f64>(&missing_data(), "test");
+}
+
+#[test]
+fn class_indices() {
+    let arff = ArffDataSet::from_str(
+        "@RELATION test\n\
+         @ATTRIBUTE x NUMERIC\n\
+         @ATTRIBUTE y {b, a, c}\n\
+         @DATA\n\
+         1, a\n\
+         2, c\n\
+         3, b\n",
+    ).unwrap();
+    let data = DataSet::from_arff(arff, Some("y"));
+
+    let (x, y, _) = load_x_classes::<f64>(&data, "test");
+
+    assert_eq!(x, vec![1.0, 2.0, 3.0]);
+    assert_eq!(y, vec![1, 2, 0]);
+}
+
+#[test]
+fn missing_masks_follow_folds() {
+    use procedures::FrozenSets;
+
+    let mut fold = Fold::new(0, 0);
+    fold.trainset = vec![0, 3];
+    fold.testset = vec![2, 1];
+
+    let procedure = FrozenSets {
+        folds: vec![vec![fold]],
+    };
+
+    let masks = fold_masks(&procedure, &[false, true, false, true]);
+
+    assert_eq!(
+        masks,
+        vec![MissingMask {
+            repeat: 0,
+            fold: 0,
+            train: vec![false, true],
+            test: vec![false, true],
+        }]
+    );
+}
+
+#[test]
+fn report_failed_folds() {
+    use procedures::FrozenSets;
+
+    let folds: Vec<_> = (0..3)
+        .map(|f| {
+            let mut fold = Fold::new(0, f);
+            fold.trainset = (0..3).filter(|&i| i != f).collect();
+            fold.testset = vec![f];
+            fold
+        })
+        .collect();
+    let procedure = FrozenSets {
+        folds: vec![folds],
+    };
+
+    let x = [10, 11, 12];
+    let y = [0, 1, 2];
+
+    let mut results = vec![];
+    let (n_folds, failures) = run_folds_fallible(
+        &procedure,
+        |i| &x[i],
+        &y,
+        |_, test| {
+            let pred: Vec<_> = test.map(|&x| x - 10).collect();
+            match pred[0] {
+                0 => Err("no training data"),
+                1 => panic!("model diverged"),
+                _ => Ok(Box::new(pred.into_iter()) as Box<Iterator<Item = _>>),
+            }
+        },
+        |fold, i, known, pred| results.push((fold.fold, i, *known, pred)),
+    );
+
+    assert_eq!(n_folds, 3);
+    assert_eq!(results, vec![(2, 2, 2, 2)]);
+    assert_eq!(
+        failures,
+        vec![
+            FoldFailure {
+                repeat: 0,
+                fold: 0,
+                error: FoldError::Error("no training data"),
+            },
+            FoldFailure {
+                repeat: 0,
+                fold: 1,
+                error: FoldError::Panic("model diverged".to_owned()),
+            },
+        ]
+    );
+}
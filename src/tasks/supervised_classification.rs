@@ -1,10 +1,16 @@
-use arff::dynamic::de::from_dataset;
 use serde::de::DeserializeOwned;
 
-use dataset::DataSet;
+use dataset::{DataSet, FeatureInfo};
+use encoding::{Encoder, NominalEncoding};
+use learner::Classifier;
 use measure_accumulator::MeasureAccumulator;
+use predictions::Predictions;
 use procedures::Procedure;
 
+use super::report::RunReport;
+use super::runner::{fold_masks, load_x_classes, load_xy, load_y, require_dense, run_folds,
+                    run_folds_fallible, MissingMask};
+
 /// Classification task
 pub struct SupervisedClassification {
     pub(crate) id: String,
@@ -25,6 +31,38 @@ impl SupervisedClassification {
         &self.name
     }
 
+    /// get the data set the task is defined on
+    pub fn source_data(&self) -> &DataSet {
+        &self.source_data
+    }
+
+    /// get per-column information about the task's data set
+    pub fn features(&self) -> &[FeatureInfo] {
+        self.source_data.features()
+    }
+
+    /// get the class labels in the order of the nominal target's declaration; class index `i`
+    /// corresponds to `class_labels()[i]`. Returns `None` if the target is not nominal.
+    pub fn class_labels(&self) -> Option<Vec<String>> {
+        self.source_data.target_categories()
+    }
+
+    /// get the row indices of the training and testing set of a fold, or `None` if the fold does
+    /// not exist
+    pub fn fold_rows(&self, repeat: usize, fold: usize) -> Option<(&[usize], &[usize])> {
+        self.estimation_procedure
+            .iter()
+            .find(|f| f.repeat == repeat && f.fold == fold)
+            .map(|f| (f.trainset.as_slice(), f.testset.as_slice()))
+    }
+
+    /// get a mask of rows with missing feature values for each fold, in the order in which the
+    /// folds are passed to the flow. Flows receive missing values as `None` if they use
+    /// `Option<_>` feature types.
+    pub fn missing_masks(&self) -> Vec<MissingMask> {
+        fold_masks(&*self.estimation_procedure, &self.source_data.missing_mask())
+    }
+
     /// run task, specifying the type of an entire feature column in `X`. This allows to run
     /// machine learning models that take features of different types, or named features in form
     /// of structs.
@@ -35,26 +73,13 @@ impl SupervisedClassification {
         Y: DeserializeOwned,
         M: MeasureAccumulator<Y>,
     {
-        let (dx, dy) = self.source_data
-            .clone_split()
-            .expect("Supervised Classification requires a target column");
-
-        let x: Vec<X> = from_dataset(&dx).unwrap();
-        let y: Vec<Y> = from_dataset(&dy).unwrap();
+        let (x, y, _) = load_xy(&self.source_data, "Supervised Classification");
 
         let mut measure = M::new();
 
-        for fold in self.estimation_procedure.iter() {
-            let mut train = fold.trainset.iter().map(|&i| (&x[i], &y[i]));
-
-            let mut test = fold.testset.iter().map(|&i| &x[i]);
-
-            let predictit = flow(&mut train, &mut test);
-
-            for (known, pred) in fold.testset.iter().map(|&i| &y[i]).zip(predictit) {
-                measure.update_one(known, &pred);
-            }
-        }
+        run_folds(&*self.estimation_procedure, |i| &x[i], &y, flow, |_, _, known, pred| {
+            measure.update_one(known, &pred)
+        });
 
         measure
     }
@@ -69,31 +94,234 @@ impl SupervisedClassification {
         Y: DeserializeOwned,
         M: MeasureAccumulator<Y>,
     {
-        let (dx, dy) = self.source_data
-            .clone_split()
-            .expect("Supervised Classification requires a target column");
+        let (x, y, n_cols) = load_xy(&self.source_data, "Supervised Classification");
+
+        let mut measure = M::new();
+
+        run_folds(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            flow,
+            |_, _, known, pred| measure.update_one(known, &pred),
+        );
+
+        measure
+    }
+
+    /// like `run`, but the flow may fail on individual folds by returning an error or by
+    /// panicking. Failed folds are skipped and listed in the report; the measure is computed from
+    /// the remaining folds.
+    pub fn run_fallible<X, Y, E, F, M>(&self, flow: F) -> RunReport<M, E>
+    where
+        F: Fn(&mut Iterator<Item = (&[X], &Y)>, &mut Iterator<Item = &[X]>)
+            -> Result<Box<Iterator<Item = Y>>, E>,
+        X: DeserializeOwned,
+        Y: DeserializeOwned,
+        M: MeasureAccumulator<Y>,
+    {
+        let (x, y, n_cols) = load_xy(&self.source_data, "Supervised Classification");
 
-        let x: Vec<X> = from_dataset(&dx).unwrap();
-        let y: Vec<Y> = from_dataset(&dy).unwrap();
+        let mut measure = M::new();
+
+        let (n_folds, failures) = run_folds_fallible(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            flow,
+            |_, _, known, pred| measure.update_one(known, &pred),
+        );
+
+        RunReport::new(measure, n_folds, failures)
+    }
+
+    /// like `run_static`, but the flow may fail on individual folds. Failed folds are handled as
+    /// in `run_fallible`.
+    pub fn run_static_fallible<X, Y, E, F, M>(&self, flow: F) -> RunReport<M, E>
+    where
+        F: Fn(&mut Iterator<Item = (&X, &Y)>, &mut Iterator<Item = &X>)
+            -> Result<Box<Iterator<Item = Y>>, E>,
+        X: DeserializeOwned,
+        Y: DeserializeOwned,
+        M: MeasureAccumulator<Y>,
+    {
+        let (x, y, _) = load_xy(&self.source_data, "Supervised Classification");
 
         let mut measure = M::new();
 
-        for fold in self.estimation_procedure.iter() {
-            let mut train = fold.trainset
-                .iter()
-                .map(|&i| (&x[i * dx.n_cols()..(i + 1) * dx.n_cols()], &y[i]));
+        let (n_folds, failures) = run_folds_fallible(
+            &*self.estimation_procedure,
+            |i| &x[i],
+            &y,
+            flow,
+            |_, _, known, pred| measure.update_one(known, &pred),
+        );
 
-            let mut test = fold.testset
-                .iter()
-                .map(|&i| &x[i * dx.n_cols()..(i + 1) * dx.n_cols()]);
+        RunReport::new(measure, n_folds, failures)
+    }
 
-            let predictit = flow(&mut train, &mut test);
+    /// run task with a model that implements `Classifier`. For each fold, the model is trained
+    /// on the training set with `fit` and then predicts every row of the testing set.
+    pub fn evaluate<X, C, L, M>(&self, learner: &mut L) -> M
+    where
+        L: Classifier<X, C>,
+        X: DeserializeOwned,
+        C: DeserializeOwned,
+        M: MeasureAccumulator<C>,
+    {
+        let (x, y, n_cols) = load_xy(&self.source_data, "Supervised Classification");
 
-            for (known, pred) in fold.testset.iter().map(|&i| &y[i]).zip(predictit) {
-                measure.update_one(known, &pred);
-            }
-        }
+        let mut measure = M::new();
+
+        run_folds(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            |train, test| {
+                let (x_train, y_train): (Vec<_>, Vec<_>) = train.unzip();
+                learner.fit(&x_train, &y_train);
+                test.map(|x| learner.predict(x)).collect::<Vec<_>>()
+            },
+            |_, _, known, pred| measure.update_one(known, &pred),
+        );
+
+        measure
+    }
+
+    /// run task on features encoded as `f64`, so that models that expect numeric features can be
+    /// used with data sets that contain nominal features. The column names of the encoded
+    /// features are available from `Encoder::fit(task.source_data(), encoding)`.
+    pub fn run_encoded<Y, F, M>(&self, encoding: NominalEncoding, flow: F) -> M
+    where
+        F: Fn(&mut Iterator<Item = (&[f64], &Y)>, &mut Iterator<Item = &[f64]>)
+            -> Box<Iterator<Item = Y>>,
+        Y: DeserializeOwned,
+        M: MeasureAccumulator<Y>,
+    {
+        require_dense(&self.source_data);
+        let encoder = Encoder::fit(&self.source_data, encoding);
+        let x = encoder.transform(&self.source_data);
+        let n_cols = encoder.n_cols();
+        let y = load_y(&self.source_data, "Supervised Classification");
+
+        let mut measure = M::new();
+
+        run_folds(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            flow,
+            |_, _, known, pred| measure.update_one(known, &pred),
+        );
+
+        measure
+    }
+
+    /// run task on a sparse data set. Feature rows are passed to the flow as `(column, value)`
+    /// pairs; omitted values are zero. See `SparseFeatures` for details.
+    pub fn run_sparse<Y, F, M>(&self, flow: F) -> M
+    where
+        F: Fn(&mut Iterator<Item = (&[(usize, f64)], &Y)>, &mut Iterator<Item = &[(usize, f64)]>)
+            -> Box<Iterator<Item = Y>>,
+        Y: DeserializeOwned,
+        M: MeasureAccumulator<Y>,
+    {
+        let sparse = self
+            .source_data
+            .sparse_features()
+            .expect("the data set is dense; use `run` instead of `run_sparse`");
+        let y = load_y(&self.source_data, "Supervised Classification");
+
+        let mut measure = M::new();
+
+        run_folds(
+            &*self.estimation_procedure,
+            |i| sparse.row(i),
+            &y,
+            flow,
+            |_, _, known, pred| measure.update_one(known, &pred),
+        );
 
         measure
     }
+
+    /// like `run_static`, but additionally records every prediction. The predictions can be
+    /// written in OpenML's predictions ARFF format.
+    pub fn run_static_recorded<X, Y, F, M>(&self, flow: F) -> (M, Predictions<Y>)
+    where
+        F: Fn(&mut Iterator<Item = (&X, &Y)>, &mut Iterator<Item = &X>) -> Box<Iterator<Item = Y>>,
+        X: DeserializeOwned,
+        Y: DeserializeOwned + Clone,
+        M: MeasureAccumulator<Y>,
+    {
+        let (x, y, _) = load_xy(&self.source_data, "Supervised Classification");
+
+        let mut measure = M::new();
+        let mut predictions = Predictions::new(&self.id, self.class_labels());
+
+        run_folds(&*self.estimation_procedure, |i| &x[i], &y, flow, |fold, i, known, pred| {
+            measure.update_one(known, &pred);
+            predictions.record(fold, i, known, pred);
+        });
+
+        (measure, predictions)
+    }
+
+    /// like `run`, but additionally records every prediction. The predictions can be written in
+    /// OpenML's predictions ARFF format.
+    pub fn run_recorded<X, Y, F, M>(&self, flow: F) -> (M, Predictions<Y>)
+    where
+        F: Fn(&mut Iterator<Item = (&[X], &Y)>, &mut Iterator<Item = &[X]>)
+            -> Box<Iterator<Item = Y>>,
+        X: DeserializeOwned,
+        Y: DeserializeOwned + Clone,
+        M: MeasureAccumulator<Y>,
+    {
+        let (x, y, n_cols) = load_xy(&self.source_data, "Supervised Classification");
+
+        let mut measure = M::new();
+        let mut predictions = Predictions::new(&self.id, self.class_labels());
+
+        run_folds(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            flow,
+            |fold, i, known, pred| {
+                measure.update_one(known, &pred);
+                predictions.record(fold, i, known, pred);
+            },
+        );
+
+        (measure, predictions)
+    }
+
+    /// like `run`, but the flow receives the target as dense class indices (`0` to `n - 1` for
+    /// `n` classes), regardless of how the labels are spelled. Predictions are recorded, so they
+    /// can be mapped back to labels with `Predictions::label`.
+    pub fn run_indexed<X, F, M>(&self, flow: F) -> (M, Predictions<usize>)
+    where
+        F: Fn(&mut Iterator<Item = (&[X], &usize)>, &mut Iterator<Item = &[X]>)
+            -> Box<Iterator<Item = usize>>,
+        X: DeserializeOwned,
+        M: MeasureAccumulator<usize>,
+    {
+        let (x, y, n_cols) = load_x_classes(&self.source_data, "Supervised Classification");
+
+        let mut measure = M::new();
+        let mut predictions = Predictions::new(&self.id, self.class_labels());
+
+        run_folds(
+            &*self.estimation_procedure,
+            |i| &x[i * n_cols..(i + 1) * n_cols],
+            &y,
+            flow,
+            |fold, i, known, pred| {
+                measure.update_one(known, &pred);
+                predictions.record(fold, i, known, pred);
+            },
+        );
+
+        (measure, predictions)
+    }
 }
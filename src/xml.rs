@@ -0,0 +1,67 @@
+//! Minimal helpers for writing and reading the XML documents exchanged with OpenML
+
+use std::borrow::Cow;
+
+/// Namespace of OpenML XML documents
+pub(crate) const OML_NAMESPACE: &str = "http://openml.org/openml";
+
+/// escape special characters in XML text content
+pub(crate) fn escape(s: &str) -> Cow<str> {
+    if !s.contains(&['&', '<', '>', '"', '\''][..]) {
+        return Cow::from(s);
+    }
+
+    let mut out = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    Cow::from(out)
+}
+
+/// replace XML entities with the characters they represent
+pub(crate) fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// append a simple `<oml:tag>text</oml:tag>` element
+pub(crate) fn push_element(out: &mut String, indent: usize, tag: &str, text: &str) {
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+    out.push_str(&format!("<oml:{}>{}</oml:{}>\n", tag, escape(text), tag));
+}
+
+/// get the text content of the first element named `tag`, if there is one
+///
+/// This is not a general XML parser; it is only intended for the short, flat responses returned by
+/// the OpenML API.
+pub(crate) fn find_element(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+
+    Some(unescape(xml[start..end].trim()))
+}
+
+#[test]
+fn xml_roundtrip() {
+    let mut doc = String::new();
+    push_element(&mut doc, 0, "name", "a < b & 'c'");
+
+    assert_eq!(doc, "<oml:name>a &lt; b &amp; &apos;c&apos;</oml:name>\n");
+    assert_eq!(find_element(&doc, "oml:name"), Some("a < b & 'c'".to_owned()));
+    assert_eq!(find_element(&doc, "oml:id"), None);
+}